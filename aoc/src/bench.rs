@@ -0,0 +1,95 @@
+use std::{fs, path::{Path, PathBuf}};
+use anyhow::{anyhow, bail, Context, Result};
+use common::{bench::{compare, Change, Measurement}, Day, Part};
+
+use crate::{cli::BenchArgs, find_day, DAYS};
+
+fn baseline_path(dir: &str, name: &str) -> PathBuf {
+    Path::new(dir).join(format!("{}.json", name))
+}
+
+fn load_baseline(path: &Path) -> Result<Vec<Measurement>> {
+    let file = fs::read_to_string(path)
+        .with_context(|| format!("reading baseline {}", path.display()))?;
+    Ok(serde_json::from_str(&file)?)
+}
+
+/// Save `results` over any earlier measurements of the same day and part,
+/// so a baseline can be built up a day at a time.
+fn save_baseline(path: &Path, results: Vec<Measurement>) -> Result<()> {
+    let mut saved = match path.exists() {
+        true => load_baseline(path)?,
+        false => Vec::new(),
+    };
+    saved.retain(|old| !results.iter().any(|new| new.day == old.day && new.part == old.part));
+    saved.extend(results);
+    saved.sort_by_key(|m| (m.day, u8::from(m.part)));
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&saved)?)
+        .with_context(|| format!("writing baseline {}", path.display()))
+}
+
+pub fn run(args: &BenchArgs) -> Result<()> {
+    let days: Vec<&Day> = match args.day {
+        Some(day) => vec![find_day(day)?],
+        None => DAYS.iter().collect(),
+    };
+    let parts = match args.part {
+        Some(part) => vec![Part::try_from(part).map_err(|n| anyhow!("there is no part {}", n))?],
+        None => vec![Part::One, Part::Two],
+    };
+    let baseline = match &args.baseline {
+        Some(name) => load_baseline(&baseline_path(&args.baseline_dir, name))?,
+        None => Vec::new(),
+    };
+
+    let mut results = Vec::new();
+    let mut regressions = 0;
+    for day in days {
+        let path = Path::new(&args.inputs).join(format!("day{}.txt", day.day));
+        if !path.exists() {
+            if args.day.is_some() {
+                bail!("no input at {}", path.display());
+            }
+            println!("Day {:>2}: no input at {}", day.day, path.display());
+            continue;
+        }
+        let file = fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+
+        for &part in &parts {
+            let measurement = day.bench(part, &file, args.samples)?;
+            let old = baseline.iter().find(|m| m.day == day.day && m.part == part);
+
+            println!("Day {:>2} {:?}", day.day, part);
+            let phases = [
+                ("parse", measurement.parse, old.map(|m| m.parse)),
+                ("solve", measurement.solve, old.map(|m| m.solve)),
+            ];
+            for (phase, stats, old) in phases {
+                match old {
+                    Some(old) => {
+                        let change = compare(&old, &stats, args.threshold);
+                        if let Change::Regressed(_) = change {
+                            regressions += 1;
+                        }
+                        println!("  {}  {}  {}", phase, stats, change);
+                    }
+                    None => println!("  {}  {}", phase, stats),
+                }
+            }
+            results.push(measurement);
+        }
+    }
+
+    if let Some(name) = &args.save_baseline {
+        save_baseline(&baseline_path(&args.baseline_dir, name), results)?;
+    }
+    match &args.baseline {
+        Some(name) if regressions > 0 => bail!("{} phase(s) regressed against baseline {}", regressions, name),
+        _ => Ok(()),
+    }
+}
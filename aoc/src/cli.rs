@@ -0,0 +1,69 @@
+use clap::{Args, Parser, Subcommand};
+use common::Output;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run one part of a day, or every registered day
+    Run(RunArgs),
+    /// Time the parse and solve phases of one day, or of every day with an input
+    Bench(BenchArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Day to run
+    #[arg(required_unless_present = "all")]
+    pub day: Option<u8>,
+    /// Part to run
+    #[arg(required_unless_present = "all", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Files to parse, `-` for stdin
+    #[arg(required_unless_present = "all")]
+    pub paths: Vec<String>,
+    /// Run both parts of every registered day
+    #[arg(long, conflicts_with_all = ["day", "part", "paths"])]
+    pub all: bool,
+    /// Directory holding a `dayN.txt` input per day, used with --all
+    #[arg(long, default_value = "inputs")]
+    pub inputs: String,
+    #[command(flatten)]
+    pub output: Output,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Day to benchmark, every registered day if left out
+    pub day: Option<u8>,
+    /// Part to benchmark, both if left out
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// Directory holding a `dayN.txt` input per day
+    #[arg(long, default_value = "inputs")]
+    pub inputs: String,
+    /// Timed runs of each phase
+    #[arg(long, default_value_t = 100)]
+    pub samples: usize,
+    /// Save the results under this name
+    #[arg(long)]
+    pub save_baseline: Option<String>,
+    /// Compare with a saved baseline, failing if anything regressed
+    #[arg(long)]
+    pub baseline: Option<String>,
+    /// Percentage change in mean time that counts as a regression or improvement
+    #[arg(long, default_value_t = 5.0)]
+    pub threshold: f64,
+    /// Directory baselines are saved in
+    #[arg(long, default_value = "target/aoc-bench")]
+    pub baseline_dir: String,
+}
+
+pub fn parse() -> Cli {
+    Cli::parse()
+}
@@ -0,0 +1,78 @@
+use std::{fs, path::Path};
+use anyhow::{anyhow, bail, Context, Result};
+use common::{Day, Format, Output, Part};
+
+mod bench;
+mod cli;
+
+const DAYS: [Day; 12] = [
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+    Day::of::<day4::Day4>(),
+    Day::of::<day7::Day7>(),
+    Day::of::<day8::Day8>(),
+    Day::of::<day9::Day9>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day15::Day15>(),
+    Day::of::<day19::Day19>(),
+];
+
+fn find_day(day: u8) -> Result<&'static Day> {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| anyhow!("day {} is not registered", day))
+}
+
+fn run_all(inputs: &Path, output: &Output) -> Result<()> {
+    let labelled = output.format == Format::Text && !output.quiet;
+    let mut answers = output.answer_file.open()?;
+    let mut mismatches = 0;
+    for day in &DAYS {
+        let path = inputs.join(format!("day{}.txt", day.day));
+        if !path.exists() {
+            if labelled {
+                println!("Day {:>2}: no input at {}", day.day, path.display());
+            }
+            continue;
+        }
+        let file = fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        for part in [Part::One, Part::Two] {
+            let report = day.run(part, &file)?;
+            match labelled {
+                true => println!("Day {:>2} {:?}: {}", day.day, part, report.answer),
+                false => output.print(&report)?,
+            }
+            if let Err(e) = output.answer_file.settle(&mut answers, &file, &report) {
+                mismatches += 1;
+                eprintln!("{}", e);
+            }
+        }
+    }
+    output.answer_file.close(answers)?;
+
+    match mismatches {
+        0 => Ok(()),
+        n => bail!("{} answers did not match {}", n, output.answer_file.answers),
+    }
+}
+
+fn main() -> Result<()> {
+    let args = cli::parse();
+    match args.command {
+        cli::Command::Run(run) if run.all => run_all(Path::new(&run.inputs), &run.output)?,
+        cli::Command::Run(run) => {
+            // clap guarantees these are present without --all
+            let day = find_day(run.day.unwrap())?;
+            let part = Part::try_from(run.part.unwrap())
+                .map_err(|n| anyhow!("there is no part {}", n))?;
+
+            run.output.print_each(&run.paths, |input| day.run(part, input))?;
+        }
+        cli::Command::Bench(args) => bench::run(&args)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Which runs of cells along a row touch which single cells, including diagonally,
+//! worked out once so each query is a lookup rather than a search of the grid.
+use std::{collections::HashMap, ops::Range};
+
+use crate::grid::NEIGHBOURS_8;
+
+/// A run of cells along one row, such as a number
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Span<T> {
+    pub row: usize,
+    pub cols: Range<usize>,
+    pub value: T,
+}
+
+/// A single cell, such as a symbol
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Point<T> {
+    pub row: usize,
+    pub col: usize,
+    pub value: T,
+}
+
+#[derive(Debug)]
+pub struct Adjacency<S, P> {
+    spans: Vec<Span<S>>,
+    points: Vec<Point<P>>,
+    /// The span covering each cell, by index into `spans`
+    span_cells: HashMap<(usize, usize), usize>,
+    point_cells: HashMap<(usize, usize), usize>,
+    /// For each span, the points touching it, in order
+    span_points: Vec<Vec<usize>>,
+    /// For each point, the spans touching it, in order
+    point_spans: Vec<Vec<usize>>,
+}
+
+impl<S, P> Adjacency<S, P> {
+    /// Index `spans` and `points`, which should not overlap
+    pub fn new(spans: Vec<Span<S>>, points: Vec<Point<P>>) -> Self {
+        let span_cells: HashMap<_, _> = spans
+            .iter()
+            .enumerate()
+            .flat_map(|(id, span)| span.cols.clone().map(move |col| ((span.row, col), id)))
+            .collect();
+        let point_cells = points.iter().enumerate().map(|(id, p)| ((p.row, p.col), id)).collect();
+
+        let mut adjacency = Adjacency {
+            spans,
+            points,
+            span_cells,
+            point_cells,
+            span_points: Vec::new(),
+            point_spans: Vec::new(),
+        };
+        adjacency.point_spans = adjacency
+            .points
+            .iter()
+            .map(|p| adjacency.span_ids_around(p.row, p.col))
+            .collect();
+        adjacency.span_points = vec![Vec::new(); adjacency.spans.len()];
+        for (point, spans) in adjacency.point_spans.iter().enumerate() {
+            for &span in spans {
+                adjacency.span_points[span].push(point);
+            }
+        }
+        adjacency
+    }
+
+    fn span_ids_around(&self, row: usize, col: usize) -> Vec<usize> {
+        let mut ids: Vec<usize> = NEIGHBOURS_8
+            .iter()
+            .filter_map(|&(dc, dr)| {
+                let cell = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+                self.span_cells.get(&cell).copied()
+            })
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    pub fn spans(&self) -> &[Span<S>] {
+        &self.spans
+    }
+
+    pub fn points(&self) -> &[Point<P>] {
+        &self.points
+    }
+
+    /// The span covering `(row, col)`, if any
+    pub fn span_at(&self, row: usize, col: usize) -> Option<&Span<S>> {
+        self.span_cells.get(&(row, col)).map(|&id| &self.spans[id])
+    }
+
+    pub fn point_at(&self, row: usize, col: usize) -> Option<&Point<P>> {
+        self.point_cells.get(&(row, col)).map(|&id| &self.points[id])
+    }
+
+    /// The spans touching the cell at `(row, col)`, each once however many of its cells do
+    pub fn spans_around(&self, row: usize, col: usize) -> Vec<&Span<S>> {
+        let ids = match self.point_cells.get(&(row, col)) {
+            Some(&point) => self.point_spans[point].clone(),
+            None => self.span_ids_around(row, col),
+        };
+        ids.into_iter().map(|id| &self.spans[id]).collect()
+    }
+
+    /// The points touching the `span`th span
+    pub fn points_around(&self, span: usize) -> impl Iterator<Item = &Point<P>> {
+        self.span_points[span].iter().map(|&id| &self.points[id])
+    }
+
+    /// Every span with at least one point touching it
+    pub fn touched_spans(&self) -> impl Iterator<Item = &Span<S>> {
+        self.spans.iter().zip(&self.span_points).filter(|(_, points)| !points.is_empty()).map(|(span, _)| span)
+    }
+
+    /// Every point with the spans touching it
+    pub fn points_with_spans(&self) -> impl Iterator<Item = (&Point<P>, Vec<&Span<S>>)> {
+        self.points
+            .iter()
+            .zip(&self.point_spans)
+            .map(|(point, spans)| (point, spans.iter().map(|&id| &self.spans[id]).collect()))
+    }
+
+    /// Every point touching exactly `k` spans, with those spans
+    pub fn points_touching(&self, k: usize) -> impl Iterator<Item = (&Point<P>, Vec<&Span<S>>)> {
+        self.points_with_spans().filter(move |(_, spans)| spans.len() == k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_and_points_that_touch() {
+        // 12.*
+        // .#.3
+        // 45..
+        let spans = vec![
+            Span { row: 0, cols: 0..2, value: 12 },
+            Span { row: 1, cols: 3..4, value: 3 },
+            Span { row: 2, cols: 0..2, value: 45 },
+        ];
+        let points = vec![Point { row: 0, col: 3, value: '*' }, Point { row: 1, col: 1, value: '#' }];
+        let index = Adjacency::new(spans, points);
+
+        assert_eq!(index.span_at(2, 1).unwrap().value, 45);
+        assert_eq!(index.point_at(1, 1).unwrap().value, '#');
+        assert!(index.span_at(1, 1).is_none());
+
+        let around = |row, col| index.spans_around(row, col).iter().map(|s| s.value).collect::<Vec<_>>();
+        assert_eq!(around(1, 1), vec![12, 45]);
+        assert_eq!(around(0, 3), vec![3]);
+        // not a point, but still somewhere
+        assert_eq!(around(1, 2), vec![12, 3, 45]);
+
+        assert_eq!(index.points_around(0).map(|p| p.value).collect::<Vec<_>>(), vec!['#']);
+        assert_eq!(index.points_around(1).map(|p| p.value).collect::<Vec<_>>(), vec!['*']);
+        assert_eq!(index.touched_spans().count(), 3);
+
+        let pairs: Vec<_> = index.points_touching(2).map(|(p, spans)| (p.value, spans.len())).collect();
+        assert_eq!(pairs, vec![('#', 2)]);
+        assert_eq!(index.points_touching(0).count(), 0);
+    }
+}
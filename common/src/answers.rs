@@ -0,0 +1,199 @@
+use std::{collections::BTreeMap, fs, path::Path};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::Report;
+
+/// Known answers, as `[dayN.partM]` tables mapping an input's hash to its answer.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Default, Debug)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>);
+
+/// FNV-1a of the input, ignoring trailing whitespace so an editor
+/// adding or removing the final newline doesn't lose the answer.
+pub fn input_hash(input: &str) -> String {
+    let hash = input
+        .trim_end()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+impl Answers {
+    /// Load answers from `path`, which doesn't have to exist yet.
+    pub fn load(path: &Path) -> Result<Answers> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let file = fs::read_to_string(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&file).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.0
+            .get(&format!("day{}", day))?
+            .get(&format!("part{}", part))?
+            .get(&input_hash(input))
+            .map(String::as_str)
+    }
+
+    pub fn record(&mut self, input: &str, report: &Report) -> Result<()> {
+        let part = answered_part(report)?;
+        self.0
+            .entry(format!("day{}", report.day))
+            .or_default()
+            .entry(format!("part{}", part))
+            .or_default()
+            .insert(input_hash(input), report.answer.clone());
+        Ok(())
+    }
+
+    /// Fail unless `report` matches the answer recorded for `input`.
+    pub fn check(&self, input: &str, report: &Report) -> Result<()> {
+        let part = answered_part(report)?;
+        let expected = self.get(report.day, part, input).ok_or_else(|| {
+            anyhow!(
+                "no answer recorded for day {} part {} with input {}",
+                report.day, part, input_hash(input)
+            )
+        })?;
+        if expected != report.answer {
+            bail!(
+                "day {} part {} answered {}, but {} is recorded",
+                report.day, part, report.answer, expected
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Answers are kept by part, so one to neither part can't be
+fn answered_part(report: &Report) -> Result<u8> {
+    report
+        .part
+        .map(u8::from)
+        .ok_or_else(|| anyhow!("day {} answered neither part, so there's nothing to check or record", report.day))
+}
+
+/// Checking answers against, or recording them in, an answers file.
+#[derive(Args, Debug)]
+pub struct AnswerFile {
+    /// Compare each answer with the one recorded for its input, failing if they differ
+    #[arg(long)]
+    pub check: bool,
+    /// Record each answer against its input
+    #[arg(long, conflicts_with = "check")]
+    pub record: bool,
+    /// File answers are kept in
+    #[arg(long, default_value = "answers.toml")]
+    pub answers: String,
+}
+
+impl AnswerFile {
+    /// The recorded answers, if `--check` or `--record` needs them.
+    pub fn open(&self) -> Result<Option<Answers>> {
+        match self.check || self.record {
+            true => Answers::load(Path::new(&self.answers)).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Fail if asked to check or record while `option` changes what's answered,
+    /// since answers are kept only by day, part and input.
+    pub fn refuse_variant(&self, option: &str) -> Result<()> {
+        match self.check || self.record {
+            true => bail!("answers are only checked or recorded for the puzzle itself, not with {}", option),
+            false => Ok(()),
+        }
+    }
+
+    /// Check or record `report`, as asked for.
+    pub fn settle(&self, answers: &mut Option<Answers>, input: &str, report: &Report) -> Result<()> {
+        match answers {
+            Some(answers) if self.check => answers.check(input, report),
+            Some(answers) => answers.record(input, report),
+            None => Ok(()),
+        }
+    }
+
+    /// Write recorded answers back out.
+    pub fn close(&self, answers: Option<Answers>) -> Result<()> {
+        match answers {
+            Some(answers) if self.record => answers.save(Path::new(&self.answers)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::Part;
+
+    fn report(answer: &str) -> Report {
+        Report {
+            day: 9,
+            part: Some(Part::One),
+            answer: answer.to_string(),
+            parse: Duration::ZERO,
+            solve: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn hash_ignores_trailing_newline() {
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(input_hash("a\n"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("a\nb"), input_hash("a"));
+    }
+
+    #[test]
+    fn record_then_check() {
+        let mut answers = Answers::default();
+        answers.record("0 3 6 9 12 15\n", &report("18")).unwrap();
+
+        assert!(answers.check("0 3 6 9 12 15\n", &report("18")).is_ok());
+        assert_eq!(
+            answers.check("0 3 6 9 12 15\n", &report("17")).unwrap_err().to_string(),
+            "day 9 part 1 answered 17, but 18 is recorded"
+        );
+        assert!(answers.check("1 3 6 10 15 21\n", &report("28")).is_err());
+
+        let neither = Report { part: None, ..report("18") };
+        assert_eq!(
+            answers.record("0 3 6 9 12 15\n", &neither).unwrap_err().to_string(),
+            "day 9 answered neither part, so there's nothing to check or record"
+        );
+    }
+
+    #[test]
+    fn variants_are_not_settled() {
+        let answer_file = |check, record| AnswerFile { check, record, answers: "answers.toml".to_string() };
+        assert!(answer_file(false, false).refuse_variant("--rule").is_ok());
+        assert_eq!(
+            answer_file(true, false).refuse_variant("--rule").unwrap_err().to_string(),
+            "answers are only checked or recorded for the puzzle itself, not with --rule"
+        );
+        assert!(answer_file(false, true).refuse_variant("--rule").is_err());
+    }
+
+    #[test]
+    fn answers_as_toml() {
+        let mut answers = Answers::default();
+        answers.record("a", &report("18")).unwrap();
+
+        let file = toml::to_string(&answers).unwrap();
+        assert_eq!(file, "[day9.part1]\naf63dc4c8601ec8c = \"18\"\n");
+        assert_eq!(toml::from_str::<Answers>(&file).unwrap(), answers);
+    }
+}
@@ -0,0 +1,188 @@
+//! Repeated timing of the parse and solve phases, summarised so that
+//! runs can be compared with each other and against a saved baseline.
+use std::{fmt, hint::black_box, time::{Duration, Instant}};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::Part;
+
+/// Summary of a set of timings.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort_unstable();
+        let n = samples.len();
+        if n == 0 {
+            return Stats { samples: 0, mean: Duration::ZERO, median: Duration::ZERO, stddev: Duration::ZERO };
+        }
+
+        let nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let median = match n % 2 {
+            0 => (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0,
+            _ => nanos[n / 2],
+        };
+        // sample standard deviation, a single run has none
+        let variance = match n {
+            1 => 0.0,
+            _ => nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        Stats {
+            samples: n,
+            mean: Duration::from_nanos(mean.round() as u64),
+            median: Duration::from_nanos(median.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:>10.2?}  median {:>10.2?}  stddev {:>10.2?}",
+            self.mean, self.median, self.stddev
+        )
+    }
+}
+
+/// Timings for one part of a day.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: Part,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Time `parse` and `solve` separately, `samples` times each,
+/// after a few untimed runs to warm caches and the thread pool.
+/// `solve` is always handed the same parsed value.
+pub fn measure<P>(
+    day: u8,
+    part: Part,
+    samples: usize,
+    parse: impl Fn() -> Result<P>,
+    solve: impl Fn(&P) -> Result<String>,
+) -> Result<Measurement> {
+    let warm_up = (samples / 10).max(1);
+
+    for _ in 0..warm_up {
+        black_box(parse()?);
+    }
+    let mut parse_times = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let timer = Instant::now();
+        let parsed = black_box(parse()?);
+        parse_times.push(timer.elapsed());
+        drop(parsed);
+    }
+
+    let parsed = parse()?;
+    for _ in 0..warm_up {
+        black_box(solve(&parsed)?);
+    }
+    let mut solve_times = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let timer = Instant::now();
+        black_box(solve(black_box(&parsed))?);
+        solve_times.push(timer.elapsed());
+    }
+
+    Ok(Measurement {
+        day,
+        part,
+        parse: Stats::from_samples(&mut parse_times),
+        solve: Stats::from_samples(&mut solve_times),
+    })
+}
+
+/// How a measurement moved relative to its baseline, as a percentage of the old mean.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Change {
+    Improved(f64),
+    Regressed(f64),
+    /// Within the threshold, or within the noise of the two runs
+    NoChange(f64),
+}
+
+/// Compare means, only calling it a change when the difference is larger than
+/// `threshold` percent and larger than the spread of both sets of samples.
+pub fn compare(baseline: &Stats, current: &Stats, threshold: f64) -> Change {
+    let old = baseline.mean.as_nanos() as f64;
+    let new = current.mean.as_nanos() as f64;
+    if old == 0.0 {
+        return Change::NoChange(0.0);
+    }
+
+    let percent = (new - old) / old * 100.0;
+    let noise = (baseline.stddev + current.stddev).as_nanos() as f64;
+    if percent.abs() <= threshold || (new - old).abs() <= noise {
+        Change::NoChange(percent)
+    } else if percent > 0.0 {
+        Change::Regressed(percent)
+    } else {
+        Change::Improved(percent)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Improved(p) => write!(f, "{:+.1}% improved", p),
+            Change::Regressed(p) => write!(f, "{:+.1}% REGRESSED", p),
+            Change::NoChange(p) => write!(f, "{:+.1}% no change", p),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(&mut micros(&[4, 2, 9, 4, 5, 4, 7, 5]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(stats.median, Duration::from_micros(4) + Duration::from_nanos(500));
+        // sqrt(32 / 7)
+        assert_eq!(stats.stddev, Duration::from_nanos(2_138));
+    }
+
+    #[test]
+    fn single_sample_has_no_spread() {
+        let stats = Stats::from_samples(&mut micros(&[3]));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn compare_against_baseline() {
+        let stats = |mean, stddev| Stats {
+            samples: 100,
+            mean: Duration::from_micros(mean),
+            median: Duration::from_micros(mean),
+            stddev: Duration::from_micros(stddev),
+        };
+        let baseline = stats(100, 2);
+
+        assert_eq!(compare(&baseline, &stats(120, 2), 5.0), Change::Regressed(20.0));
+        assert_eq!(compare(&baseline, &stats(80, 2), 5.0), Change::Improved(-20.0));
+        assert_eq!(compare(&baseline, &stats(103, 2), 5.0), Change::NoChange(3.0));
+        // past the threshold, but not outside the noise
+        assert_eq!(compare(&baseline, &stats(110, 20), 5.0), Change::NoChange(10.0));
+    }
+}
@@ -0,0 +1,87 @@
+use std::{error::Error, fmt};
+use nom::error::ErrorKind;
+
+/// A piece of puzzle input that could not be parsed, pinned to where it is in the file.
+#[derive(PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters, where parsing gave up
+    pub column: usize,
+    pub kind: ErrorKind,
+    /// The offending line, for pointing at the problem
+    pub text: String,
+}
+
+impl ParseError {
+    /// Locate a nom error within `input`.
+    /// The error's remaining input must be a slice of `input`,
+    /// which is the case for anything parsed from `lines`, `split` etc.
+    pub fn locate(input: &str, err: nom::error::Error<&str>) -> Self {
+        let offset = (err.input.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+
+        let (before, after) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        let line_end = after.find('\n').map_or(input.len(), |n| offset + n);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind: err.code,
+            text: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "failed to parse line {}, column {} ({})",
+            self.line,
+            self.column,
+            self.kind.description()
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_error_in_input() {
+        let input = "Game 1: 3 blue\nGame 2: 4 grean\nGame 3: 1 red\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = nom::error::Error::new(&line[10..], ErrorKind::Tag);
+
+        let actual = ParseError::locate(input, err);
+        assert_eq!(actual, ParseError {
+            line: 2,
+            column: 11,
+            kind: ErrorKind::Tag,
+            text: "Game 2: 4 grean".to_string(),
+        });
+        assert_eq!(
+            actual.to_string(),
+            "failed to parse line 2, column 11 (Tag)\n  Game 2: 4 grean\n            ^"
+        );
+    }
+
+    #[test]
+    fn locate_error_at_end_of_line() {
+        let input = "1abc2\nabcdef";
+        let line = input.lines().nth(1).unwrap();
+        let err = nom::error::Error::new(&line[line.len()..], ErrorKind::Digit);
+
+        let actual = ParseError::locate(input, err);
+        assert_eq!((actual.line, actual.column), (2, 7));
+        assert_eq!(actual.text, "abcdef");
+    }
+}
@@ -0,0 +1,274 @@
+//! A rectangular grid of cells, stored row after row with each row padded out
+//! to whole 16 byte chunks so SIMD code can load any row chunk by chunk.
+use std::{mem::size_of, ops::{Index, IndexMut}};
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+
+/// Bytes per SIMD chunk, and the alignment of the first row
+pub const CHUNK_BYTES: usize = 16;
+
+/// Up, right, down, left
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Clockwise from up-left
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (1, 0), (1, 1), (0, 1),
+    (-1, 1), (-1, 0),
+];
+
+#[derive(Debug)]
+pub struct Grid<T> {
+    /// Padded storage, with some slack at the front to align the first row
+    cells: Vec<T>,
+    offset: usize,
+    width: usize,
+    height: usize,
+    row_size: usize,
+}
+
+impl<T: Copy + Send + Sync> Grid<T> {
+    /// A `width` by `height` grid with every cell, padding included, set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        let per_chunk = (CHUNK_BYTES / size_of::<T>().max(1)).max(1);
+        let row_size = width.next_multiple_of(per_chunk);
+
+        let cells = vec![fill; row_size * height + per_chunk];
+        // only when T doesn't divide up a chunk can this fail to find an offset
+        let offset = match cells.as_ptr().align_offset(CHUNK_BYTES) {
+            offset if offset < per_chunk => offset,
+            _ => 0,
+        };
+
+        Grid { cells, offset, width, height, row_size }
+    }
+
+    /// Build a grid from the lines of `input`, handing each line and the
+    /// padded row it becomes to `parse_row`, with the rows done in parallel.
+    /// Every line has to be as wide as the first.
+    pub fn parse_rows(input: &str, fill: T, parse_row: impl Fn(&[u8], &mut [T]) + Sync + Send) -> Result<Self> {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let width = common_width(lines.iter().map(|l| l.len()))?;
+
+        let mut grid = Grid::new(width, lines.len(), fill);
+        let row_size = grid.row_size;
+        if row_size > 0 {
+            lines
+                .into_par_iter()
+                .zip(grid.as_mut_slice().par_chunks_mut(row_size))
+                .for_each(|(line, row)| parse_row(line, row));
+        }
+        Ok(grid)
+    }
+
+    /// Build a grid from the lines of `input`, turning each byte into a cell.
+    pub fn parse(input: &str, fill: T, cell: impl Fn(u8) -> T + Sync + Send) -> Result<Self> {
+        Self::parse_rows(input, fill, |line, row| {
+            for (byte, out) in line.iter().zip(row) {
+                *out = cell(*byte);
+            }
+        })
+    }
+
+    /// As [`Grid::parse`], but a cell per character rather than per byte,
+    /// so a row is as wide as it has characters.
+    pub fn parse_chars(input: &str, fill: T, cell: impl Fn(char) -> T + Sync + Send) -> Result<Self> {
+        let lines: Vec<&str> = input.lines().collect();
+        let width = common_width(lines.iter().map(|l| l.chars().count()))?;
+
+        let mut grid = Grid::new(width, lines.len(), fill);
+        let row_size = grid.row_size;
+        if row_size > 0 {
+            lines
+                .into_par_iter()
+                .zip(grid.as_mut_slice().par_chunks_mut(row_size))
+                .for_each(|(line, row)| {
+                    for (chr, out) in line.chars().zip(row) {
+                        *out = cell(chr);
+                    }
+                });
+        }
+        Ok(grid)
+    }
+}
+
+/// A fresh allocation needs its own offset to align the first row,
+/// so the cells are copied into a new grid rather than cloned.
+impl<T: Copy + Send + Sync> Clone for Grid<T> {
+    fn clone(&self) -> Self {
+        let mut grid = Grid::new(self.width, self.height, self.cells[0]);
+        grid.as_mut_slice().copy_from_slice(self.as_slice());
+        grid
+    }
+}
+
+/// The width every row has, or which row doesn't
+fn common_width(mut widths: impl Iterator<Item = usize>) -> Result<usize> {
+    let width = widths.next().unwrap_or(0);
+    if let Some((y, other)) = widths.enumerate().find(|(_, w)| *w != width) {
+        bail!("row {} is {} wide, but the first row is {}", y + 2, other, width);
+    }
+    Ok(width)
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Cells per row including padding, so also the distance between vertically adjacent cells
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    /// Every cell, padding included
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells[self.offset..self.offset + self.row_size * self.height]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells[self.offset..self.offset + self.row_size * self.height]
+    }
+
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        y * self.row_size + x
+    }
+
+    /// The `(x, y)` of an index into [`Grid::as_slice`]
+    pub fn coord_of(&self, index: usize) -> (usize, usize) {
+        (index % self.row_size, index / self.row_size)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        match x < self.width && y < self.height {
+            true => Some(&self.as_slice()[self.index_of(x, y)]),
+            false => None,
+        }
+    }
+
+    /// Row `y` without its padding
+    pub fn row(&self, y: usize) -> &[T] {
+        let start = self.index_of(0, y);
+        &self.as_slice()[start..start + self.width]
+    }
+
+    /// Each row without its padding
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Each row with its padding, so a whole number of SIMD chunks
+    pub fn padded_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.as_slice().chunks_exact(self.row_size.max(1))
+    }
+
+    /// The coordinates `offsets` away from `(x, y)` that are on the grid.
+    pub fn neighbours<'a>(&self, x: usize, y: usize, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+
+    /// Up, right, down and left of `(x, y)`, where on the grid
+    pub fn neighbours_4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &NEIGHBOURS_4)
+    }
+
+    /// All eight cells around `(x, y)`, where on the grid
+    pub fn neighbours_8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &NEIGHBOURS_8)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.as_slice()[self.index_of(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let index = self.index_of(x, y);
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_padded_and_aligned() {
+        let grid = Grid::parse("#..#.\n.#...\n", b'.', |b| b).unwrap();
+        assert_eq!((grid.width(), grid.height(), grid.row_size()), (5, 2, 16));
+        assert_eq!(grid.as_slice().as_ptr() as usize % CHUNK_BYTES, 0);
+        assert_eq!(grid.row(1), b".#...");
+        assert!(grid.padded_rows().all(|row| row.len() == 16 && row[5..].iter().all(|b| *b == b'.')));
+
+        // four byte cells pack four to a chunk
+        let grid = Grid::new(5, 2, 0_u32);
+        assert_eq!(grid.row_size(), 8);
+        assert_eq!(grid.as_slice().as_ptr() as usize % CHUNK_BYTES, 0);
+    }
+
+    #[test]
+    fn clones_are_aligned() {
+        let grid = Grid::parse("#..#.\n.#...\n", b'.', |b| b).unwrap();
+        // the same rows, a byte further into their allocation than a clone's would be
+        let mut cells = vec![b'.'; grid.cells.len() + 1];
+        cells[1..].copy_from_slice(&grid.cells);
+        let shifted = Grid { cells, offset: grid.offset + 1, ..grid };
+
+        let clone = shifted.clone();
+        assert_eq!(clone.as_slice().as_ptr() as usize % CHUNK_BYTES, 0);
+        assert_eq!(clone.as_slice(), shifted.as_slice());
+        assert_eq!(clone.row(1), b".#...");
+    }
+
+    #[test]
+    fn coords_and_indices() {
+        let mut grid = Grid::new(20, 3, 0_u8);
+        let index = grid.index_of(17, 2);
+        assert_eq!(index, 2 * 32 + 17);
+        assert_eq!(grid.coord_of(index), (17, 2));
+
+        grid[(17, 2)] = 1;
+        assert_eq!(grid.as_slice()[index], 1);
+        assert_eq!(grid.get(17, 2), Some(&1));
+        assert_eq!(grid.get(20, 2), None);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::new(3, 3, 0_u8);
+        assert_eq!(grid.neighbours_4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours_8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours_4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours_8(2, 2).collect::<Vec<_>>(), vec![(1, 1), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let err = Grid::parse("...\n..\n", b'.', |b| b).unwrap_err();
+        assert_eq!(err.to_string(), "row 2 is 2 wide, but the first row is 3");
+    }
+
+    #[test]
+    fn a_cell_per_character() {
+        let grid = Grid::parse_chars("·★·\n1·é\n", ' ', |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], '★');
+        assert_eq!(grid.row(1), &['1', '·', 'é']);
+
+        let err = Grid::parse_chars("··\n·\n", ' ', |c| c).unwrap_err();
+        assert_eq!(err.to_string(), "row 2 is 1 wide, but the first row is 2");
+    }
+}
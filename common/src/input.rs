@@ -0,0 +1,14 @@
+use std::{fs, io::{self, Read}};
+use anyhow::{Context, Result};
+
+/// Read puzzle input from `path`, where `-` is stdin.
+pub fn read_input(path: &str) -> Result<String> {
+    match path {
+        "-" => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).context("reading stdin")?;
+            Ok(input)
+        }
+        _ => fs::read_to_string(path).with_context(|| format!("reading {}", path)),
+    }
+}
@@ -0,0 +1,97 @@
+//! Plumbing shared by the day crates and the `aoc` runner.
+use std::fmt::Display;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+pub mod adjacency;
+mod answers;
+pub mod bench;
+mod error;
+pub mod grid;
+mod input;
+mod report;
+
+pub use answers::{input_hash, AnswerFile, Answers};
+pub use error::ParseError;
+pub use input::read_input;
+pub use report::{Format, Output, Report};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(n),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's puzzle: the input is parsed once, then either part is
+/// answered from the parsed form.
+pub trait Solution {
+    const DAY: u8;
+
+    /// Whatever both parts share, often borrowing from the raw input
+    type Parsed<'a>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>>;
+    fn part_one(parsed: &Self::Parsed<'_>) -> Result<Self::PartOne>;
+    fn part_two(parsed: &Self::Parsed<'_>) -> Result<Self::PartTwo>;
+
+    /// Parse `input` and answer `part`, timing both.
+    fn run(part: Part, input: &str) -> Result<Report> where Self: Sized {
+        Report::measure(Self::DAY, part, || Self::parse(input), |parsed| match part {
+            Part::One => Ok(Self::part_one(parsed)?.to_string()),
+            Part::Two => Ok(Self::part_two(parsed)?.to_string()),
+        })
+    }
+
+    /// Time parsing and answering `part` separately, `samples` times each.
+    fn bench(part: Part, input: &str, samples: usize) -> Result<bench::Measurement> where Self: Sized {
+        bench::measure(Self::DAY, part, samples, || Self::parse(input), |parsed| match part {
+            Part::One => Ok(Self::part_one(parsed)?.to_string()),
+            Part::Two => Ok(Self::part_two(parsed)?.to_string()),
+        })
+    }
+}
+
+/// A type-erased [`Solution`], so the runner can hold every day in one list.
+pub struct Day {
+    pub day: u8,
+    run: fn(Part, &str) -> Result<Report>,
+    bench: fn(Part, &str, usize) -> Result<bench::Measurement>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Day {
+        Day { day: S::DAY, run: S::run, bench: S::bench }
+    }
+
+    pub fn run(&self, part: Part, input: &str) -> Result<Report> {
+        (self.run)(part, input)
+    }
+
+    pub fn bench(&self, part: Part, input: &str, samples: usize) -> Result<bench::Measurement> {
+        (self.bench)(part, input, samples)
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+use anyhow::{bail, Result};
+use clap::{Args, ValueEnum};
+use serde::{Serialize, Serializer};
+
+use crate::{read_input, AnswerFile, Part};
+
+/// The answer to one part of a day, and how long it took to get there.
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub day: u8,
+    /// Which part was answered, if the answer is to either
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<Part>,
+    /// Kept as text, since answers are only required to be `Display`
+    pub answer: String,
+    #[serde(rename = "parse_us", serialize_with = "micros")]
+    pub parse: Duration,
+    #[serde(rename = "solve_us", serialize_with = "micros")]
+    pub solve: Duration,
+}
+
+fn micros<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_micros())
+}
+
+impl Report {
+    /// Time `parse`, then time `solve` on what it produced.
+    pub fn measure<P>(
+        day: u8,
+        part: Part,
+        parse: impl FnOnce() -> Result<P>,
+        solve: impl FnOnce(&P) -> Result<String>,
+    ) -> Result<Report> {
+        let timer = Instant::now();
+        let parsed = parse()?;
+        let parse = timer.elapsed();
+
+        let timer = Instant::now();
+        let answer = solve(&parsed)?;
+        let solve = timer.elapsed();
+
+        Ok(Report { day, part: Some(part), answer, parse, solve })
+    }
+
+    /// The same answer, but to neither of the day's parts, such as one to
+    /// a question of the user's own
+    pub fn to_neither_part(self) -> Report {
+        Report { part: None, ..self }
+    }
+}
+
+#[derive(ValueEnum, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Format {
+    /// `Value is: ...` along with the time taken
+    Text,
+    /// One JSON object per answer
+    Json,
+}
+
+/// How answers are printed, shared by every binary's CLI.
+#[derive(Args, Debug)]
+pub struct Output {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+    /// Print only the answer
+    #[arg(long, short, conflicts_with = "format")]
+    pub quiet: bool,
+    #[command(flatten)]
+    pub answer_file: AnswerFile,
+}
+
+impl Output {
+    pub fn print(&self, report: &Report) -> Result<()> {
+        if self.quiet {
+            println!("{}", report.answer);
+            return Ok(());
+        }
+        match self.format {
+            Format::Text => println!(
+                "Value is: {} calculated in {}",
+                report.answer,
+                (report.parse + report.solve).as_micros()
+            ),
+            Format::Json => println!("{}", serde_json::to_string(report)?),
+        }
+        Ok(())
+    }
+
+    /// As [`Output::print`], naming the input the answer came from.
+    pub fn print_labelled(&self, input: &str, report: &Report) -> Result<()> {
+        #[derive(Serialize)]
+        struct Labelled<'a> {
+            input: &'a str,
+            #[serde(flatten)]
+            report: &'a Report,
+        }
+
+        if self.quiet {
+            println!("{}", report.answer);
+            return Ok(());
+        }
+        match self.format {
+            Format::Text => println!(
+                "{}: Value is: {} calculated in {}",
+                input,
+                report.answer,
+                (report.parse + report.solve).as_micros()
+            ),
+            Format::Json => println!("{}", serde_json::to_string(&Labelled { input, report })?),
+        }
+        Ok(())
+    }
+
+    /// Answer each of `paths` (`-` being stdin) with `run`, printing one answer per input
+    /// and checking or recording it if asked to.
+    /// With several inputs a failure is reported and the rest still run.
+    pub fn print_each(&self, paths: &[String], run: impl Fn(&str) -> Result<Report>) -> Result<()> {
+        let mut answers = self.answer_file.open()?;
+
+        let mut failed = 0;
+        for path in paths {
+            let result = read_input(path).and_then(|input| {
+                let report = run(&input)?;
+                match paths.len() {
+                    1 => self.print(&report)?,
+                    _ => self.print_labelled(path, &report)?,
+                }
+                self.answer_file.settle(&mut answers, &input, &report)
+            });
+            match result {
+                Ok(()) => {}
+                Err(e) if paths.len() == 1 => return Err(e),
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("{}: {:#}", path, e);
+                }
+            }
+        }
+        self.answer_file.close(answers)?;
+
+        match failed {
+            0 => Ok(()),
+            n => bail!("{} of {} inputs failed", n, paths.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_as_json() {
+        let report = Report {
+            day: 9,
+            part: Some(Part::Two),
+            answer: "1234".to_string(),
+            parse: Duration::from_micros(15),
+            solve: Duration::from_nanos(2_500),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(json, r#"{"day":9,"part":2,"answer":"1234","parse_us":15,"solve_us":2}"#);
+
+        let json = serde_json::to_string(&report.to_neither_part()).unwrap();
+        assert_eq!(json, r#"{"day":9,"answer":"1234","parse_us":15,"solve_us":2}"#);
+    }
+
+    #[test]
+    fn failures_do_not_stop_other_inputs() {
+        let output = Output {
+            format: Format::Text,
+            quiet: true,
+            answer_file: AnswerFile { check: false, record: false, answers: "answers.toml".to_string() },
+        };
+        let paths = ["does/not/exist.txt".to_string(), "Cargo.toml".to_string()];
+        let runs = std::cell::Cell::new(0);
+        let result = output.print_each(&paths, |_| {
+            runs.set(runs.get() + 1);
+            Report::measure(1, Part::One, || Ok(()), |_| Ok("42".to_string()))
+        });
+
+        assert_eq!(runs.get(), 1);
+        assert_eq!(result.unwrap_err().to_string(), "1 of 2 inputs failed");
+    }
+}
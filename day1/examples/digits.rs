@@ -0,0 +1,67 @@
+//! Times part one's line parsers on a generated input of the given size, e.g.
+//! `cargo run --release -p day1 --example digits -- 16 200` for 16MB and 200 samples
+use anyhow::{Context, Result};
+use common::{bench, Part};
+use day1::part_one;
+use nom::error::Error;
+use rayon::prelude::*;
+
+type Parser = for<'a> fn(&'a str) -> Result<usize, Error<&'a str>>;
+
+const PARSERS: [(&str, Parser); 2] = [
+    ("chars", part_one::parse_line_chars),
+    ("simd", part_one::parse_line),
+];
+
+/// Lines of 20 to 80 lowercase letters with a few digits scattered through them,
+/// from a fixed xorshift seed so every run sees the same input
+fn generate(bytes: usize) -> String {
+    let mut state = 0x2545f4914f6cdd1d_u64;
+    let mut next = move |below: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % below
+    };
+
+    let mut input = String::with_capacity(bytes + 81);
+    while input.len() < bytes {
+        let len = 20 + next(61);
+        for _ in 0..len {
+            let byte = match next(16) {
+                0 => b'0' + next(10) as u8,
+                _ => b'a' + next(26) as u8,
+            };
+            input.push(byte as char);
+        }
+        // every line needs at least one digit
+        input.push((b'1' + next(9) as u8) as char);
+        input.push('\n');
+    }
+    input
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let megabytes: usize = match args.next() {
+        Some(mb) => mb.parse().context("size should be a number of megabytes")?,
+        None => 8,
+    };
+    let samples = match args.next() {
+        Some(samples) => samples.parse().context("samples should be a number")?,
+        None => 100,
+    };
+    let input = generate(megabytes << 20);
+
+    for (name, parse_line) in PARSERS {
+        let measurement = bench::measure(1, Part::One, samples, || Ok(()), |_| {
+            let sum = input
+                .par_lines()
+                .map(|line| parse_line(line).map_err(|e| e.input.len()))
+                .sum::<Result<usize, _>>();
+            Ok(format!("{:?}", sum))
+        })?;
+        println!("{:>5}  {}", name, measurement.solve);
+    }
+    Ok(())
+}
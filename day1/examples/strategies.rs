@@ -0,0 +1,38 @@
+//! Times each way of answering part two on the same input, e.g.
+//! `cargo run --release -p day1 --example strategies -- input.txt 200`
+use anyhow::{Context, Result};
+use common::{bench, read_input, Part};
+use day1::{part_two, vocab::Vocabulary};
+use nom::error::Error;
+use rayon::prelude::*;
+
+type Strategy = for<'a> fn(&Vocabulary, &'a str) -> Result<usize, Error<&'a str>>;
+
+const STRATEGIES: [(&str, Strategy); 3] = [
+    ("regex", part_two::parse_line_re_with),
+    ("aho", part_two::parse_line_aho_with),
+    ("scan", part_two::parse_line_scan_with),
+];
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().context("expected an input file")?;
+    let samples = match args.next() {
+        Some(samples) => samples.parse().context("samples should be a number")?,
+        None => 100,
+    };
+    let input = read_input(&path)?;
+    let vocab = Vocabulary::english();
+
+    for (name, parse_line) in STRATEGIES {
+        let measurement = bench::measure(1, Part::Two, samples, || Ok(()), |_| {
+            let sum = input
+                .par_lines()
+                .map(|line| parse_line(&vocab, line).map_err(|e| e.input.len()))
+                .sum::<Result<usize, _>>();
+            Ok(format!("{:?}", sum))
+        })?;
+        println!("{:>5}  {}", name, measurement.solve);
+    }
+    Ok(())
+}
@@ -1,16 +1,40 @@
 use clap::{command, Parser};
+use common::Output;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// File to parse
-    pub path: String,
-    /// Part two
+    /// Files to parse, `-` for stdin
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+    /// Part two, searching forwards for the first digit and backwards for the last
     #[arg(long)]
     pub part_two: bool,
-    /// Part two aho
-    #[arg(long)]
+    /// With `--part-two`, find every word with Aho-Corasick, so overlapping words still count
+    #[arg(long, requires = "part_two")]
     pub part_two_aho: bool,
+    /// With `--part-two`, find words with a regex, which misses the second of two overlapping words
+    #[arg(long, requires = "part_two", conflicts_with = "part_two_aho")]
+    pub part_two_re: bool,
+    /// Words for the digits in part two: english, french, german, roman,
+    /// or a file of `word digit` lines
+    #[arg(long, default_value = "english")]
+    pub vocab: String,
+    /// Run every strategy over each line and report the lines they disagree on,
+    /// rather than answering
+    #[arg(long, conflicts_with_all = ["part_two", "part_two_aho", "part_two_re"])]
+    pub compare: bool,
+    /// Print each line with the tokens of its calibration value highlighted, rather than answering
+    #[arg(long, conflicts_with = "compare")]
+    pub explain: bool,
+    /// Tokens to take from each end of a line, found as `--part-two` finds them
+    #[arg(long, default_value_t = 1, conflicts_with_all = ["part_two_aho", "part_two_re", "compare", "explain"])]
+    pub count: usize,
+    /// Base to read the tokens taken as the digits of
+    #[arg(long, default_value_t = 10, conflicts_with_all = ["part_two_aho", "part_two_re", "compare", "explain"])]
+    pub base: usize,
+    #[command(flatten)]
+    pub output: Output,
 }
 
 pub fn parse() -> Cli {
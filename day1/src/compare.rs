@@ -0,0 +1,96 @@
+//! Every strategy run over the same lines, to find those they don't agree on.
+use std::fmt;
+use nom::error::Error;
+use rayon::prelude::*;
+
+use crate::{part_one, part_two::{self, Ends}, vocab::Vocabulary};
+
+pub type Strategy = for<'a> fn(&Vocabulary, &'a str) -> Result<Ends, Error<&'a str>>;
+
+/// Each strategy by name, the first being part one's
+pub const STRATEGIES: [(&str, Strategy); 4] = [
+    ("digits", |_, line| part_one::ends(line)),
+    ("regex", part_two::ends_re),
+    ("aho", part_two::ends_aho),
+    ("scan", part_two::ends_scan),
+];
+
+/// A line the strategies gave different calibration values for,
+/// with what each of them found
+#[derive(Debug)]
+pub struct Disagreement<'a> {
+    /// Counting from 1
+    pub line_number: usize,
+    pub line: &'a str,
+    pub found: Vec<(&'static str, Option<Ends>)>,
+}
+
+impl Disagreement<'_> {
+    fn value((_, ends): &(&str, Option<Ends>)) -> Option<usize> {
+        ends.clone().map(part_two::calibration)
+    }
+}
+
+/// The lines of `input` that not every strategy gives the same value for.
+pub fn compare<'a>(vocab: &Vocabulary, input: &'a str) -> Vec<Disagreement<'a>> {
+    let lines: Vec<&str> = input.lines().collect();
+    lines
+        .into_par_iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let found: Vec<_> = STRATEGIES
+                .iter()
+                .map(|(name, ends)| (*name, ends(vocab, line).ok()))
+                .collect();
+            let value = Disagreement::value(&found[0]);
+            match found.iter().all(|f| Disagreement::value(f) == value) {
+                true => None,
+                false => Some(Disagreement { line_number: i + 1, line, found }),
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Disagreement<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "line {}: {}", self.line_number, self.line)?;
+        for found in &self.found {
+            match found {
+                (name, Some((first, last))) => writeln!(
+                    f,
+                    "  {:<6} {:>2}  {} at {:?}, {} at {:?}",
+                    name,
+                    Disagreement::value(found).unwrap_or_default(),
+                    &self.line[first.span.clone()],
+                    first.span,
+                    &self.line[last.span.clone()],
+                    last.span,
+                )?,
+                (name, None) => writeln!(f, "  {:<6}  -  nothing found", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlaps_are_disagreements() {
+        let input = "two1nine\nxtwone\n7pqrst\nabc\n";
+        let disagreements = compare(&Vocabulary::english(), input);
+
+        let lines: Vec<usize> = disagreements.iter().map(|d| d.line_number).collect();
+        assert_eq!(lines, vec![1, 2]);
+        assert_eq!(
+            disagreements[1].to_string(),
+            "line 2: xtwone\n  \
+               digits  -  nothing found\n  \
+               regex  22  two at 1..4, two at 1..4\n  \
+               aho    21  two at 1..4, one at 3..6\n  \
+               scan   21  two at 1..4, one at 3..6\n"
+        );
+    }
+}
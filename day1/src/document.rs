@@ -0,0 +1,67 @@
+use nom::error::Error;
+use rayon::prelude::*;
+
+use crate::{compare::Strategy, part_one, part_two::{calibration, Ends}, vocab::Vocabulary};
+
+/// One line of the calibration document
+#[derive(Debug)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// Its digits alone, as part one reads them, or why there are none
+    pub digits: Result<Ends, Error<&'a str>>,
+}
+
+impl<'a> Line<'a> {
+    /// Part one's calibration value
+    pub fn digits_value(&self) -> Result<usize, Error<&'a str>> {
+        match &self.digits {
+            Ok(ends) => Ok(calibration(ends.clone())),
+            // nom's errors aren't `Clone`
+            Err(e) => Err(Error::new(e.input, e.code)),
+        }
+    }
+
+    /// Part two's calibration value, finding spelled out numbers from `vocab`
+    /// with `spelled`
+    pub fn spelled_value(&self, vocab: &Vocabulary, spelled: Strategy) -> Result<usize, Error<&'a str>> {
+        spelled(vocab, self.text).map(calibration)
+    }
+}
+
+#[derive(Debug)]
+pub struct Document<'a> {
+    /// The whole input, to locate lines without digits in
+    pub input: &'a str,
+    pub lines: Vec<Line<'a>>,
+}
+
+impl<'a> Document<'a> {
+    /// Read the digits of every line of `input`. Lines without any are kept,
+    /// failing only part one. Spelled out numbers are left to part two, which
+    /// picks how to search for them.
+    pub fn parse(input: &'a str) -> Self {
+        let lines = input
+            .par_lines()
+            .map(|text| Line { text, digits: part_one::ends(text) })
+            .collect();
+        Document { input, lines }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+    use crate::part_two::ends_scan;
+
+    #[test]
+    fn lines_read_both_ways() {
+        let vocab = Vocabulary::english();
+        let document = Document::parse("two1nine\neightwothree\nabcdef");
+
+        let digits: Vec<_> = document.lines.iter().map(Line::digits_value).collect();
+        assert_eq!(digits[..2], [Ok(11), Err(Error::new("", ErrorKind::Digit))]);
+        let spelled: Vec<_> = document.lines.iter().map(|line| line.spelled_value(&vocab, ends_scan)).collect();
+        assert_eq!(spelled, vec![Ok(29), Ok(83), Err(Error::new("", ErrorKind::Alt))]);
+    }
+}
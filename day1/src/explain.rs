@@ -0,0 +1,62 @@
+//! Lines drawn with the tokens making up their calibration value picked out.
+use nom::error::Error;
+
+use crate::part_two::{calibration, Ends};
+
+const FIRST: &str = "\x1b[1;32m";
+const LAST: &str = "\x1b[1;36m";
+/// Where the first and last tokens overlap, as in `twone`
+const BOTH: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// `line` with its first token in green and its last in cyan.
+pub fn highlight(line: &str, (first, last): &Ends) -> String {
+    let mut out = String::with_capacity(line.len() + 32);
+    let mut current = None;
+    for (i, chr) in line.char_indices() {
+        let style = match (first.span.contains(&i), last.span.contains(&i)) {
+            (true, true) => Some(BOTH),
+            (true, false) => Some(FIRST),
+            (false, true) => Some(LAST),
+            (false, false) => None,
+        };
+        if style != current {
+            out.push_str(style.unwrap_or(RESET));
+            current = style;
+        }
+        out.push(chr);
+    }
+    if current.is_some() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// One line of `--explain` output, numbering lines from 1.
+pub fn explain(line_number: usize, line: &str, ends: Result<Ends, Error<&str>>) -> String {
+    match ends {
+        Ok(ends) => format!("{:>5} {:>3}  {}", line_number, calibration(ends.clone()), highlight(line, &ends)),
+        Err(_) => format!("{:>5}   -  {}", line_number, line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_two::ends_scan, vocab::Vocabulary};
+
+    #[test]
+    fn highlighted_tokens() {
+        let vocab = Vocabulary::english();
+        let ends = ends_scan(&vocab, "xtwone").unwrap();
+        assert_eq!(
+            highlight("xtwone", &ends),
+            "x\x1b[1;32mtw\x1b[1;33mo\x1b[1;36mne\x1b[0m"
+        );
+
+        let line = "a7b";
+        let ends = ends_scan(&vocab, line).unwrap();
+        assert_eq!(explain(12, line, Ok(ends)), "   12  77  a\x1b[1;33m7\x1b[0mb");
+        assert_eq!(explain(3, "abc", ends_scan(&vocab, "abc")), "    3   -  abc");
+    }
+}
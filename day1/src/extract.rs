@@ -0,0 +1,103 @@
+//! Calibration values built from any number of tokens at each end of a line,
+//! read in any base.
+use anyhow::{bail, Result};
+use nom::error::{Error, ErrorKind};
+
+use crate::vocab::Vocabulary;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Extractor {
+    /// Tokens taken from each end of the line
+    count: usize,
+    base: usize,
+}
+
+impl Default for Extractor {
+    /// The puzzle's own, the first and last token as a two digit decimal number
+    fn default() -> Self {
+        Extractor { count: 1, base: 10 }
+    }
+}
+
+impl Extractor {
+    pub fn new(count: usize, base: usize) -> Result<Self> {
+        if count == 0 {
+            bail!("at least one token has to be taken from each end");
+        }
+        if base < 2 {
+            bail!("base {} has no digits to write numbers with", base);
+        }
+        Ok(Extractor { count, base })
+    }
+
+    /// The leading then the trailing tokens of `line` as the digits of a number.
+    /// Tokens are found as [`crate::part_two::ends_scan`] finds them, so with the defaults
+    /// this is the same calibration value, and the leading and trailing tokens may be the same ones.
+    pub fn extract<'a>(&self, vocab: &Vocabulary, line: &'a str) -> Result<usize, Error<&'a str>> {
+        let leading = vocab.leading(line, self.count);
+        let trailing = vocab.trailing(line, self.count);
+        match leading.len() {
+            0 => return Err(Error::new(&line[line.len()..], ErrorKind::Alt)),
+            n if n < self.count => return Err(Error::new(&line[line.len()..], ErrorKind::Count)),
+            _ => {}
+        }
+
+        leading.iter().chain(&trailing).try_fold(0_usize, |acc, token| {
+            if token.value >= self.base {
+                return Err(Error::new(&line[token.span.start..], ErrorKind::Verify));
+            }
+            acc.checked_mul(self.base)
+                .and_then(|acc| acc.checked_add(token.value))
+                .ok_or_else(|| Error::new(&line[token.span.start..], ErrorKind::TooLarge))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part_two::parse_line_scan;
+
+    #[test]
+    fn defaults_match_part_two() {
+        let vocab = Vocabulary::english();
+        let extractor = Extractor::default();
+        for line in ["two1nine", "xtwone3four", "twone3threeight", "treb7uchet", "abc"] {
+            assert_eq!(extractor.extract(&vocab, line), parse_line_scan(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn two_from_each_end() {
+        let extractor = Extractor::new(2, 10).unwrap();
+        assert_eq!(extractor.extract(&Vocabulary::digits(), "a1b2c3d4"), Ok(1234));
+        assert_eq!(extractor.extract(&Vocabulary::digits(), "a1b2c3"), Ok(1223));
+        assert_eq!(extractor.extract(&Vocabulary::english(), "sixtwone8x"), Ok(6218));
+        assert_eq!(
+            extractor.extract(&Vocabulary::digits(), "abc7def"),
+            Err(Error::new("", ErrorKind::Count))
+        );
+    }
+
+    #[test]
+    fn other_bases() {
+        let vocab = Vocabulary::digits();
+        assert_eq!(Extractor::new(1, 8).unwrap().extract(&vocab, "1abc7"), Ok(0o17));
+        assert_eq!(Extractor::new(2, 2).unwrap().extract(&vocab, "1x0y1z1"), Ok(0b1011));
+        assert_eq!(Extractor::new(1, 16).unwrap().extract(&vocab, "9a9"), Ok(0x99));
+        assert_eq!(
+            Extractor::new(1, 2).unwrap().extract(&vocab, "1ab2"),
+            Err(Error::new("2", ErrorKind::Verify))
+        );
+        assert!(Extractor::new(1, 1).is_err());
+        assert!(Extractor::new(0, 10).is_err());
+    }
+
+    #[test]
+    fn too_many_digits() {
+        // the 21st digit overflows
+        let line = "1".repeat(24);
+        let extractor = Extractor::new(12, 10).unwrap();
+        assert_eq!(extractor.extract(&Vocabulary::digits(), &line), Err(Error::new("1111", ErrorKind::TooLarge)));
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use rayon::prelude::*;
+
+pub mod part_one;
+pub mod part_two;
+pub mod compare;
+pub mod document;
+pub mod explain;
+pub mod extract;
+pub mod vocab;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Parsed<'a> = document::Document<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Ok(document::Document::parse(input))
+    }
+
+    fn part_one(document: &Self::Parsed<'_>) -> Result<usize> {
+        document
+            .lines
+            .par_iter()
+            .map(document::Line::digits_value)
+            .sum::<Result<usize, _>>()
+            .map_err(|e| ParseError::locate(document.input, e).into())
+    }
+
+    fn part_two(document: &Self::Parsed<'_>) -> Result<usize> {
+        part_two_with(document, &part_two::ENGLISH, part_two::ends_scan)
+    }
+}
+
+/// Part two, finding spelled out numbers from `vocab` with `spelled`
+pub fn part_two_with(document: &document::Document, vocab: &vocab::Vocabulary, spelled: compare::Strategy) -> Result<usize> {
+    document
+        .lines
+        .par_iter()
+        .map(|line| line.spelled_value(vocab, spelled))
+        .sum::<Result<usize, _>>()
+        .map_err(|e| ParseError::locate(document.input, e).into())
+}
@@ -1,26 +1,111 @@
-use std::fs;
 use rayon::prelude::*;
 use anyhow::Result;
+use common::{read_input, ParseError, Part, Report, Solution};
+use day1::{
+    compare::{compare, Strategy},
+    explain::explain,
+    extract::Extractor,
+    part_one, part_two,
+    vocab::Vocabulary,
+    Day1,
+};
 
 mod cli;
-mod part_one;
-mod part_two;
 
+/// The strategy the part flags ask for
+fn strategy(args: &cli::Cli) -> Strategy {
+    match (args.part_two, args.part_two_aho, args.part_two_re) {
+        (false, _, _) => |_, line| part_one::ends(line),
+        (true, true, _) => part_two::ends_aho,
+        (true, _, true) => part_two::ends_re,
+        _ => part_two::ends_scan,
+    }
+}
+
+/// The option making this run answer something other than the puzzle, if any
+fn variant(args: &cli::Cli, extractor: &Extractor) -> Option<&'static str> {
+    // the vocabulary only matters to part two
+    match (args.part_two_aho, args.part_two_re, args.part_two && args.vocab != "english") {
+        (true, _, _) => Some("--part-two-aho"),
+        (_, true, _) => Some("--part-two-re"),
+        (_, _, true) => Some("--vocab"),
+        _ if extractor != &Extractor::default() => Some("--count or --base"),
+        _ => None,
+    }
+}
+
+fn print_explanations(paths: &[String], vocab: &Vocabulary, ends: Strategy) -> Result<()> {
+    for path in paths {
+        let input = read_input(path)?;
+        if paths.len() > 1 {
+            println!("{}:", path);
+        }
+        let mut total = 0;
+        for (i, line) in input.lines().enumerate() {
+            let found = ends(vocab, line);
+            if let Ok(found) = &found {
+                total += part_two::calibration(found.clone());
+            }
+            println!("{}", explain(i + 1, line, found));
+        }
+        println!("total {}", total);
+    }
+    Ok(())
+}
+
+fn print_disagreements(paths: &[String], vocab: &Vocabulary) -> Result<()> {
+    for path in paths {
+        let input = read_input(path)?;
+        if paths.len() > 1 {
+            println!("{}:", path);
+        }
+        let disagreements = compare(vocab, &input);
+        for disagreement in &disagreements {
+            println!("{}", disagreement);
+        }
+        println!("{} of {} lines disagreed", disagreements.len(), input.lines().count());
+    }
+    Ok(())
+}
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
+    let vocab = Vocabulary::load(&args.vocab)?;
+    if args.compare {
+        return print_disagreements(&args.paths, &vocab);
+    }
+    if args.explain {
+        return print_explanations(&args.paths, &vocab, strategy(&args));
+    }
+    let extractor = Extractor::new(args.count, args.base)?;
+    if let Some(option) = variant(&args, &extractor) {
+        args.output.answer_file.refuse_variant(option)?;
+    }
+    if extractor != Extractor::default() {
+        let (part, vocab) = match args.part_two {
+            true => (Part::Two, vocab),
+            false => (Part::One, Vocabulary::digits()),
+        };
+        return args.output.print_each(&args.paths, |file| {
+            Report::measure(Day1::DAY, part, || Day1::parse(file), |document| {
+                document
+                    .lines
+                    .par_iter()
+                    .map(|line| extractor.extract(&vocab, line.text))
+                    .sum::<Result<usize, _>>()
+                    .map(|total| total.to_string())
+                    .map_err(|e| ParseError::locate(file, e).into())
+            })
+        });
+    }
 
-    let parse_line = match &args.part_two {
-        false => part_one::parse_line,
-        true => match &args.part_two_aho {
-            true => part_two::parse_line_aho,
-            false => part_two::parse_line_re
-        }
-    };
-    
-    let sum: usize = lines.map(parse_line).sum();
-    println!("Value is: {}", sum);
-    Ok(()) 
+    args.output.print_each(&args.paths, |file| match &args.part_two {
+        false => Day1::run(Part::One, file),
+        true => Report::measure(
+            Day1::DAY,
+            Part::Two,
+            || Day1::parse(file),
+            |document| Ok(day1::part_two_with(document, &vocab, strategy(&args))?.to_string()),
+        ),
+    })
 }
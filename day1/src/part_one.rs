@@ -1,26 +1,76 @@
-const PLACEHOLDER_CHAR: char = ' ';
-
-pub fn parse_line(line: &str) -> usize {
-    let mut acc = String::with_capacity(2);
-    let mut last_seen = PLACEHOLDER_CHAR;
-    for chr in line.chars() {
-        match chr {
-            '0'..='9' => {
-                if last_seen == PLACEHOLDER_CHAR {
-                    acc.push(chr);
-                }
-                last_seen = chr;
-            }
-            _ => {}
+use nom::error::{Error, ErrorKind};
+use packed_simd::u8x16;
+
+use crate::{part_two::Ends, vocab::Token};
+
+const CHUNK: usize = 16;
+
+/// Bit `i` is set when byte `i` of `chunk` is an ASCII digit
+fn digit_mask(chunk: &[u8]) -> u16 {
+    let chunk = match chunk.len() {
+        CHUNK => u8x16::from_slice_unaligned(chunk),
+        len => {
+            // pad a short chunk out with bytes that aren't digits
+            let mut padded = [0; CHUNK];
+            padded[..len].copy_from_slice(chunk);
+            u8x16::from_slice_unaligned(&padded)
+        }
+    };
+    // anything below '0' wraps round to well above 10
+    (chunk - u8x16::splat(b'0')).lt(u8x16::splat(10)).bitmask()
+}
+
+fn first_digit(bytes: &[u8]) -> Option<usize> {
+    bytes
+        .chunks(CHUNK)
+        .enumerate()
+        .find_map(|(i, chunk)| match digit_mask(chunk) {
+            0 => None,
+            mask => Some(i * CHUNK + mask.trailing_zeros() as usize),
+        })
+}
+
+fn last_digit(bytes: &[u8]) -> Option<usize> {
+    let mut end = bytes.len();
+    for chunk in bytes.rchunks(CHUNK) {
+        let start = end - chunk.len();
+        match digit_mask(chunk) {
+            0 => end = start,
+            mask => return Some(start + (CHUNK - 1) - mask.leading_zeros() as usize),
         }
     }
-    acc.push(last_seen);
-    match usize::from_str_radix(&acc, 10) {
-        Ok(v) => v,
-        Err(_) => { println!("err parsing {}, from line {}", acc, line); 0 }
+    None
+}
+
+/// The first and last digits found a chunk at a time, from either end of the line.
+pub fn parse_line(line: &str) -> Result<usize, Error<&str>> {
+    let bytes = line.as_bytes();
+    match (first_digit(bytes), last_digit(bytes)) {
+        (Some(first), Some(last)) => Ok(((bytes[first] - b'0') * 10 + (bytes[last] - b'0')) as usize),
+        _ => Err(Error::new(&line[line.len()..], ErrorKind::Digit)),
     }
 }
 
+/// As [`parse_line`], a character at a time
+pub fn parse_line_chars(line: &str) -> Result<usize, Error<&str>> {
+    let mut digits = line.chars().filter_map(|chr| chr.to_digit(10));
+    let Some(first) = digits.next() else {
+        // ran off the end of the line without finding a digit
+        return Err(Error::new(&line[line.len()..], ErrorKind::Digit));
+    };
+    let last = digits.last().unwrap_or(first);
+    Ok((first * 10 + last) as usize)
+}
+
+/// Where the first and last digits are, as part two would report them
+pub fn ends(line: &str) -> Result<Ends, Error<&str>> {
+    let bytes = line.as_bytes();
+    let token = |i: usize| Token { value: (bytes[i] - b'0') as usize, span: i..i + 1 };
+    match (first_digit(bytes), last_digit(bytes)) {
+        (Some(first), Some(last)) => Ok((token(first), token(last))),
+        _ => Err(Error::new(&line[line.len()..], ErrorKind::Digit)),
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -36,7 +86,31 @@ mod tests {
         ];
         for (line, expectation) in lines {
             let result = parse_line(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
+            assert_eq!(parse_line_chars(line), Ok(expectation));
+        }
+    }
+
+    #[test]
+    fn digits_in_later_chunks() {
+        let lines = [
+            ("abcdefghijklmnop7", 77),
+            ("abcdefghijklmno1pqrstuvwxyzabcdef2gh", 12),
+            ("9abcdefghijklmnopqrstuvwxyzabcdefghij", 99),
+            ("abcdefghijklmnop3abcdefghijklmnop", 33),
+            ("abc/:def4", 44),
+        ];
+        for (line, expectation) in lines {
+            assert_eq!(parse_line(line), Ok(expectation), "{}", line);
+            assert_eq!(parse_line_chars(line), Ok(expectation), "{}", line);
         }
     }
+
+    #[test]
+    fn line_without_digits() {
+        let result = parse_line("trebuchet");
+        assert_eq!(result, Err(Error::new("", ErrorKind::Digit)));
+        assert_eq!(parse_line("abcdefghijklmnopqrstuvwxyz"), Err(Error::new("", ErrorKind::Digit)));
+        assert_eq!(parse_line_chars("trebuchet"), Err(Error::new("", ErrorKind::Digit)));
+    }
 }
@@ -1,95 +1,93 @@
-use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
-use phf::phf_map;
-use {
-    once_cell::sync::Lazy,
-    regex::Regex,
-};
-
-static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"one|two|three|four|five|six|seven|eight|nine|[0-9]").unwrap());
-
-const PATTERNS: [&str; 19] = [
-    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
-    "one",
-    "two",
-    "three",
-    "four",
-    "five",
-    "six",
-    "seven",
-    "eight",
-    "nine"
-    ];
+use aho_corasick::Match;
+use nom::error::{Error, ErrorKind};
+use once_cell::sync::Lazy;
 
-static AHO: Lazy<AhoCorasick> = Lazy::new(|| AhoCorasickBuilder::new().build(PATTERNS).unwrap());
-
-static DECODE: phf::Map<&'static str, usize> = phf_map! {
-    "0" => 0,
-    "1" => 1,
-    "2" => 2,
-    "3" => 3,
-    "4" => 4,
-    "5" => 5,
-    "6" => 6,
-    "7" => 7,
-    "8" => 8,
-    "9" => 9,
-    "one" => 1,
-    "two" => 2,
-    "three" => 3,
-    "four" => 4,
-    "five" => 5,
-    "six" => 6,
-    "seven" => 7,
-    "eight" => 8,
-    "nine" => 9
-};
-
-
-pub fn parse_line_re(line: &str) -> usize {
-    let mut matches = RE.find_iter(line);
-    let partial = if let Some(m) = matches.next() {
-        match DECODE.get(m.as_str()) {
-            Some(n) => n,
-            None => {println!("err no phf match for {}, from line {}", m.as_str(), line); return 0}
-        }
-    } else {
-        println!("err no regex matches from line {}", line);
-        return 0
+use crate::vocab::{Token, Vocabulary};
+
+pub static ENGLISH: Lazy<Vocabulary> = Lazy::new(Vocabulary::english);
+
+/// The first and last tokens of a line, the calibration value's two digits
+pub type Ends = (Token, Token);
+
+pub fn calibration((first, last): Ends) -> usize {
+    (first.value * 10) + last.value
+}
+
+pub fn parse_line_re(line: &str) -> Result<usize, Error<&str>> {
+    parse_line_re_with(&ENGLISH, line)
+}
+
+pub fn parse_line_re_with<'a>(vocab: &Vocabulary, line: &'a str) -> Result<usize, Error<&'a str>> {
+    ends_re(vocab, line).map(calibration)
+}
+
+/// Matches never overlap, so `twone` is only a 2.
+pub fn ends_re<'a>(vocab: &Vocabulary, line: &'a str) -> Result<Ends, Error<&'a str>> {
+    let mut matches = vocab.regex().find_iter(line);
+    let first = match matches.next() {
+        Some(m) => decode(vocab, line, m)?,
+        None => return Err(Error::new(&line[line.len()..], ErrorKind::Alt)),
     };
 
-    if let Some(m) = matches.last() {
-        match DECODE.get(m.as_str()) {
-            Some(n) => {
-                let rtn = (partial * 10) + n;
-                println!("ok: {}, from line {}", rtn, line);
-                rtn
-            },
-            None => {
-                println!("err no phf match for {}, from line {}", m.as_str(), line); 
-                0
-            }
-        }
-    } else {
-        println!("ok: {}{}, from line {}", partial, partial, line);
-        (partial * 10) + partial
+    match matches.last() {
+        Some(m) => Ok((first, decode(vocab, line, m)?)),
+        None => Ok((first.clone(), first)),
     }
 }
 
-pub fn parse_line_aho(line: &str) -> usize {
-    let mut matches = AHO.find_overlapping_iter(line);
-    let partial = if let Some(m) = matches.next() {
-        let n = m.pattern().as_usize();
-        if n < 10 { n } else { n - 9 }
-    } else {
-        println!("err no aho matches from line {}", line);
-        return 0
+fn decode<'a>(vocab: &Vocabulary, line: &'a str, m: regex::Match) -> Result<Token, Error<&'a str>> {
+    vocab
+        .decode(m.as_str())
+        .map(|value| Token { value, span: m.range() })
+        .ok_or_else(|| Error::new(&line[m.start()..], ErrorKind::MapOpt))
+}
+
+pub fn parse_line_aho(line: &str) -> Result<usize, Error<&str>> {
+    parse_line_aho_with(&ENGLISH, line)
+}
+
+pub fn parse_line_aho_with<'a>(vocab: &Vocabulary, line: &'a str) -> Result<usize, Error<&'a str>> {
+    ends_aho(vocab, line).map(calibration)
+}
+
+/// Words may overlap, as in `twone`, so the first digit is the match starting
+/// earliest and the last the match ending latest. A tie goes to the longer
+/// match, making `VIII` an 8 rather than a 5 or a 1.
+pub fn ends_aho<'a>(vocab: &Vocabulary, line: &'a str) -> Result<Ends, Error<&'a str>> {
+    let mut matches = vocab.automaton().find_overlapping_iter(line);
+    let Some(m) = matches.next() else {
+        return Err(Error::new(&line[line.len()..], ErrorKind::Alt));
     };
 
-    if let Some(m) = matches.last() {
-        let n = m.pattern().as_usize();
-        if n < 10 { (partial * 10) + n } else { (partial * 10) + (n - 9) }
-    } else {
-        (partial * 10) + partial
+    let (first, last) = matches.fold((m, m), |(first, last): (Match, Match), m| {
+        let first = match (m.start(), first.end()) < (first.start(), m.end()) {
+            true => m,
+            false => first,
+        };
+        let last = match (m.end(), last.start()) > (last.end(), m.start()) {
+            true => m,
+            false => last,
+        };
+        (first, last)
+    });
+    let token = |m: Match| Token { value: vocab.value_of(m.pattern()), span: m.range() };
+    Ok((token(first), token(last)))
+}
+
+pub fn parse_line_scan(line: &str) -> Result<usize, Error<&str>> {
+    parse_line_scan_with(&ENGLISH, line)
+}
+
+pub fn parse_line_scan_with<'a>(vocab: &Vocabulary, line: &'a str) -> Result<usize, Error<&'a str>> {
+    ends_scan(vocab, line).map(calibration)
+}
+
+/// As [`ends_aho`], but searching forwards for the first digit and
+/// backwards for the last rather than visiting every match in between.
+pub fn ends_scan<'a>(vocab: &Vocabulary, line: &'a str) -> Result<Ends, Error<&'a str>> {
+    match (vocab.first(line), vocab.last(line)) {
+        (Some(first), Some(last)) => Ok((first, last)),
+        _ => Err(Error::new(&line[line.len()..], ErrorKind::Alt)),
     }
 }
 
@@ -109,7 +107,7 @@ mod tests {
     fn calibration_simple_re() {
         for (line, expectation) in SIMPLE {
             let result = parse_line_re(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
 
@@ -117,7 +115,7 @@ mod tests {
     fn calibration_simple_aho() {
         for (line, expectation) in SIMPLE {
             let result = parse_line_aho(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
 
@@ -135,7 +133,7 @@ mod tests {
     fn calibration_wordy_re() {
         for (line, expectation) in WORDY {
             let result = parse_line_re(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
 
@@ -143,7 +141,7 @@ mod tests {
     fn calibration_wordy_aho() {
         for (line, expectation) in WORDY {
             let result = parse_line_aho(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
 
@@ -156,7 +154,7 @@ mod tests {
     fn calibration_overlapping_re() {
         for (line, expectation) in OVERLAPPING {
             let result = parse_line_re(line);
-            assert_ne!(result, expectation);
+            assert_ne!(result, Ok(expectation));
         }
     }
 
@@ -164,9 +162,62 @@ mod tests {
     fn calibration_overlapping_aho() {
         for (line, expectation) in OVERLAPPING {
             let result = parse_line_aho(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
+        }
+    }
+
+    #[test]
+    fn calibration_scan() {
+        for (line, expectation) in SIMPLE.iter().chain(&WORDY).chain(&OVERLAPPING) {
+            let result = parse_line_scan(line);
+            assert_eq!(result, Ok(*expectation));
+        }
+    }
+
+    #[test]
+    fn calibration_without_numbers() {
+        let line = "abcdefghijk";
+        let expected = Err(Error::new("", ErrorKind::Alt));
+        assert_eq!(parse_line_re(line), expected);
+        assert_eq!(parse_line_aho(line), expected);
+        assert_eq!(parse_line_scan(line), expected);
+    }
+
+    fn calibrate(language: &str, lines: &[(&str, usize)]) {
+        let vocab = Vocabulary::builtin(language).unwrap();
+        for &(line, expectation) in lines {
+            assert_eq!(parse_line_aho_with(&vocab, line), Ok(expectation), "{} in {}", line, language);
+            assert_eq!(parse_line_scan_with(&vocab, line), Ok(expectation), "{} in {}", line, language);
         }
     }
 
+    #[test]
+    fn calibration_french() {
+        calibrate("french", &[
+            ("deux1neuf", 29),
+            ("septrois", 73),
+            ("xhuitroisix", 86),
+            ("cinq", 55),
+        ]);
+    }
+
+    #[test]
+    fn calibration_german() {
+        calibrate("german", &[
+            ("zweins", 21),
+            ("achtzwei3", 83),
+            ("fünfzehn", 55),
+            ("sechsieben", 67),
+        ]);
+    }
 
+    #[test]
+    fn calibration_roman() {
+        calibrate("roman", &[
+            ("xVIIIy", 88),
+            ("IVIII", 48),
+            ("IX2", 92),
+            ("7XIV", 74),
+        ]);
+    }
 }
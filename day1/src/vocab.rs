@@ -0,0 +1,224 @@
+//! The words a calibration line may spell its digits with, alongside the digits themselves.
+use std::{cell::RefCell, collections::HashMap, fs, ops::Range, path::Path};
+use aho_corasick::{AhoCorasick, MatchKind, PatternID};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// Built in vocabularies, each spelling out one to nine
+pub const BUILTIN: [(&str, [&str; 9]); 4] = [
+    ("english", ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"]),
+    ("french", ["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"]),
+    ("german", ["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"]),
+    ("roman", ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"]),
+];
+
+thread_local! {
+    /// Scratch space for lines being searched backwards
+    static REVERSED: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+}
+
+/// A digit or word found in a line, and where
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Token {
+    pub value: usize,
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    /// The value of each pattern, in the order the automaton numbers them
+    values: Vec<usize>,
+    by_word: HashMap<String, usize>,
+    automaton: AhoCorasick,
+    /// Leftmost-longest, for the first token
+    forward: AhoCorasick,
+    /// Every pattern spelt backwards, searched leftmost-longest over the line spelt backwards
+    /// to find the last token without passing over the rest
+    backward: AhoCorasick,
+    regex: Regex,
+}
+
+impl Vocabulary {
+    /// A vocabulary of the digits and `words`, each paired with the digit it stands for.
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = (S, usize)>) -> Result<Self> {
+        let mut patterns: Vec<String> = DIGITS.iter().map(|d| d.to_string()).collect();
+        let mut values: Vec<usize> = (0..DIGITS.len()).collect();
+        for (word, value) in words {
+            let word = word.as_ref();
+            if word.is_empty() {
+                bail!("words can't be empty");
+            }
+            if value >= DIGITS.len() {
+                bail!("{} stands for {}, which isn't a digit", word, value);
+            }
+            patterns.push(word.to_string());
+            values.push(value);
+        }
+
+        let automaton = AhoCorasick::new(&patterns)?;
+        let mut leftmost_longest = AhoCorasick::builder();
+        leftmost_longest.match_kind(MatchKind::LeftmostLongest);
+        let forward = leftmost_longest.build(&patterns)?;
+        let backward = leftmost_longest
+            .build(patterns.iter().map(|p| p.bytes().rev().collect::<Vec<u8>>()))?;
+        // digits last, so the alternation tries words in the order given
+        let alternation: Vec<String> = patterns[DIGITS.len()..].iter().map(|w| regex::escape(w)).collect();
+        let regex = match alternation.is_empty() {
+            true => Regex::new("[0-9]")?,
+            false => Regex::new(&format!("{}|[0-9]", alternation.join("|")))?,
+        };
+        let by_word = patterns.into_iter().zip(values.iter().copied()).collect();
+
+        Ok(Vocabulary { values, by_word, automaton, forward, backward, regex })
+    }
+
+    pub fn english() -> Self {
+        Self::builtin("english").unwrap()
+    }
+
+    /// One of the [`BUILTIN`] vocabularies, by name
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (_, words) = BUILTIN.iter().find(|(builtin, _)| *builtin == name)?;
+        Some(Self::new(words.iter().zip(1..)).expect("built in vocabularies are valid"))
+    }
+
+    /// Parse a vocabulary of one `word digit` pair per line,
+    /// skipping blank lines and those starting with `#`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut words = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [word, value] => match value.parse() {
+                    Ok(value) => words.push((word, value)),
+                    Err(_) => bail!("line {}: {} isn't a digit", n + 1, value),
+                },
+                _ => bail!("line {}: expected a word and the digit it stands for", n + 1),
+            }
+        }
+        Self::new(words)
+    }
+
+    /// A built in vocabulary if `name` is one, otherwise the file at `name`.
+    pub fn load(name: &str) -> Result<Self> {
+        if let Some(vocab) = Self::builtin(name) {
+            return Ok(vocab);
+        }
+        let path = Path::new(name);
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("reading vocabulary {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("parsing vocabulary {}", path.display()))
+    }
+
+    /// Every word and digit, for overlapping search
+    pub fn automaton(&self) -> &AhoCorasick {
+        &self.automaton
+    }
+
+    /// Every word then the digits, for leftmost-first search
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    pub fn value_of(&self, pattern: PatternID) -> usize {
+        self.values[pattern.as_usize()]
+    }
+
+    pub fn decode(&self, word: &str) -> Option<usize> {
+        self.by_word.get(word).copied()
+    }
+
+    /// Only the digits, no words
+    pub fn digits() -> Self {
+        Self::new(Vec::<(&str, usize)>::new()).expect("digits alone are a valid vocabulary")
+    }
+
+    /// The token starting earliest in `line`, the longest if several do
+    pub fn first(&self, line: &str) -> Option<Token> {
+        let m = self.forward.find(line)?;
+        Some(Token { value: self.value_of(m.pattern()), span: m.range() })
+    }
+
+    /// The token ending latest in `line`, the longest if several do
+    pub fn last(&self, line: &str) -> Option<Token> {
+        self.backwards(line, |reversed| {
+            let m = self.backward.find(reversed)?;
+            Some(self.unreverse(line, m))
+        })
+    }
+
+    /// Up to `n` tokens from the start of `line`, each beginning after the one before ends
+    pub fn leading(&self, line: &str, n: usize) -> Vec<Token> {
+        self.forward
+            .find_iter(line)
+            .take(n)
+            .map(|m| Token { value: self.value_of(m.pattern()), span: m.range() })
+            .collect()
+    }
+
+    /// Up to `n` tokens from the end of `line`, each ending before the one after begins,
+    /// in the order they appear
+    pub fn trailing(&self, line: &str, n: usize) -> Vec<Token> {
+        let mut tokens: Vec<Token> = self.backwards(line, |reversed| {
+            self.backward
+                .find_iter(reversed)
+                .take(n)
+                .map(|m| self.unreverse(line, m))
+                .collect()
+        });
+        tokens.reverse();
+        tokens
+    }
+
+    /// Run `search` over `line` spelt backwards
+    fn backwards<R>(&self, line: &str, search: impl FnOnce(&[u8]) -> R) -> R {
+        REVERSED.with(|reversed| {
+            let mut reversed = reversed.borrow_mut();
+            reversed.clear();
+            reversed.extend(line.bytes().rev());
+            search(reversed.as_slice())
+        })
+    }
+
+    /// The token a match found in `line` spelt backwards stands for
+    fn unreverse(&self, line: &str, m: aho_corasick::Match) -> Token {
+        let span = line.len() - m.end()..line.len() - m.start();
+        Token { value: self.value_of(m.pattern()), span }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vocabulary_from_file() {
+        let vocab = Vocabulary::parse("# Dutch\neen 1\n\ntwee 2\n").unwrap();
+        assert_eq!(vocab.decode("twee"), Some(2));
+        assert_eq!(vocab.decode("7"), Some(7));
+        assert_eq!(vocab.decode("three"), None);
+
+        let err = Vocabulary::parse("een 1\ntwee\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected a word and the digit it stands for");
+        let err = Vocabulary::parse("tien 10\n").unwrap_err();
+        assert_eq!(err.to_string(), "tien stands for 10, which isn't a digit");
+    }
+
+    #[test]
+    fn first_and_last_tokens() {
+        let vocab = Vocabulary::english();
+        assert_eq!(vocab.first("xtwone3"), Some(Token { value: 2, span: 1..4 }));
+        assert_eq!(vocab.last("xtwone3four"), Some(Token { value: 4, span: 7..11 }));
+        assert_eq!(vocab.last("twone"), Some(Token { value: 1, span: 2..5 }));
+        assert_eq!(vocab.first("abc"), None);
+
+        // spans are in bytes
+        let vocab = Vocabulary::builtin("german").unwrap();
+        assert_eq!(vocab.last("zweifünfx"), Some(Token { value: 5, span: 4..9 }));
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use common::{grid::Grid, Solution};
+
+pub mod part_one;
+pub mod part_two;
+pub mod pipe;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed<'a> = Grid<pipe::Tile>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        pipe::parse_pipes(input)
+    }
+
+    fn part_one(grid: &Self::Parsed<'_>) -> Result<usize> {
+        part_one::farthest(grid)
+    }
+
+    fn part_two(grid: &Self::Parsed<'_>) -> Result<usize> {
+        part_two::enclosed(grid)
+    }
+}
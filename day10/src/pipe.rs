@@ -0,0 +1,47 @@
+use anyhow::Result;
+use common::grid::Grid;
+
+#[derive(PartialEq, Eq, Copy, Clone, Default, Debug)]
+pub enum Tile {
+    #[default]
+    Ground, // .
+    NorthSouth, // |
+    EastWest, // -
+    NorthEast, // L
+    NorthWest, // J
+    SouthWest, // 7
+    SouthEast, // F
+    Start, // S
+}
+
+fn parse_tile(byte: u8) -> Tile {
+    match byte {
+        b'|' => Tile::NorthSouth,
+        b'-' => Tile::EastWest, // -
+        b'L' => Tile::NorthEast, // L
+        b'J' => Tile::NorthWest, // J
+        b'7' => Tile::SouthWest, // 7
+        b'F' => Tile::SouthEast, // F
+        b'S' => Tile::Start, // S
+        _ => Tile::Ground, // .
+    }
+}
+
+pub fn parse_pipes(input: &str) -> Result<Grid<Tile>> {
+    Grid::parse(input, Tile::default(), parse_tile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipes_from_input() {
+        let grid = parse_pipes("7-F
+.SJ
+").unwrap();
+        assert_eq!(grid.get(1, 1), Some(&Tile::Start));
+        assert_eq!(grid.get(2, 0), Some(&Tile::SouthEast));
+        assert_eq!(grid.get(0, 1), Some(&Tile::Ground));
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use common::{grid::Grid, Solution};
+
+pub mod part_one;
+pub mod part_two;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed<'a> = Grid<part_one::Star>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        part_one::parse_grid(input)
+    }
+
+    fn part_one(grid: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_one::total_distance(grid))
+    }
+
+    fn part_two(grid: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_two::total_distance(grid))
+    }
+}
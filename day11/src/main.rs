@@ -1,26 +1,17 @@
-#![feature(slice_as_chunks)]
-use std::{fs, time::Instant};
 use anyhow::Result;
-use rayon::prelude::*;
+use common::{Part, Solution};
+use day11::Day11;
 
 mod cli;
-mod part_one;
-mod part_two;
 
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
 
-    let timer = Instant::now();
-    let sum: usize = match &args.part_two {
-        false => part_one::parse_lines(lines),
-        true => part_two::parse_lines(lines),
-        // true => part_two::parse_input(&file),
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    let base_time = timer.elapsed();
-    
-    println!("Value is: {} calculated in {}", sum, base_time.as_micros());
-    Ok(()) 
+
+    args.output.print_each(&args.paths, |input| Day11::run(part, input))
 }
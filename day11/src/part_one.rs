@@ -1,153 +1,126 @@
-use rayon::{prelude::*, str::Lines};
+use anyhow::Result;
+use common::grid::Grid;
+use packed_simd::u8x16;
+use rayon::prelude::*;
 
-// Grid Size 5, but smallest chunk is 16
-#[cfg(test)]
-const ROW_SIZE: usize = 16;
-#[cfg(test)]
-const GRID_ROWS: usize = 5;
-
-// Grid size 140 * 140 
-// 16 * 9 = 144 
-#[cfg(not(test))]
-const ROW_SIZE: usize = 144; 
-#[cfg(not(test))]
-const GRID_ROWS: usize = 144;
-
-const ALLOCATION: usize = ROW_SIZE * GRID_ROWS;
-
-#[derive(PartialEq, Eq, Copy, Clone, Default, Debug)]
-enum Tile {
-    #[default]
-    Ground, // .
-    NorthSouth, // |
-    EastWest, // -
-    NorthEast, // L
-    NorthWest, // J
-    SouthWest, // 7
-    SouthEast, // F
-    Start, // S
+// the grid pads rows out to whole 16 byte chunks
+const CHUNK: usize = 16;
+
+const STAR: Star = 0x01;
+pub type Star = u8;
+
+const EXPANSION: usize = 2;
+
+fn parse_line_to_stars(input: &[u8], out: &mut [Star]) {
+    let mask = u8x16::splat(STAR);
+    for (slice, out) in input.chunks(CHUNK).zip(out.chunks_exact_mut(CHUNK)) {
+        // pad the final chunk of the row out with empty space
+        let mut padded = [b'.'; CHUNK];
+        padded[..slice.len()].copy_from_slice(slice);
+        let chunk = u8x16::from_slice_unaligned(&padded);
+
+        // # is 0010 0011 & 0000 0001 = 1
+        // . is 0010 1110 & 0000 0001 = 0
+        // so we only care about the final bit
+        let val = chunk & mask;
+
+        val.write_to_slice_aligned(out);
+    }
 }
 
-fn parse_line_to_tiles<const T: usize>(input: &[u8], out: &mut [Tile; ROW_SIZE]) {
-    let mut out_idx = 0;
-    for chunk in input.chunks(T) {
-        for idx in 0..chunk.len() {
-            let tile = match chunk[idx] {
-                b'|' => Tile::NorthSouth,
-                b'-' => Tile::EastWest, // -
-                b'L' => Tile::NorthEast, // L
-                b'J' => Tile::NorthWest, // J
-                b'7' => Tile::SouthWest, // 7
-                b'F' => Tile::SouthEast, // F
-                b'S' => Tile::Start, // S
-                _ => Tile::Ground, // .
-            };
-            out[out_idx + idx] = tile;
-        }
-        out_idx += T;
-    };
+pub fn parse_grid(input: &str) -> Result<Grid<Star>> {
+    Grid::parse_rows(input, 0, parse_line_to_stars)
 }
 
-pub fn parse_lines(lines: Lines) -> usize {
-    let rows_as_bytes: Vec<_> = lines.into_par_iter().map(|l| l.as_bytes()).collect();
-    let mut full_grid = [Tile::default(); ALLOCATION];
-
-    let (mut_slices, _) = full_grid.as_chunks_mut::<ROW_SIZE>();
-
-    rows_as_bytes
-        .into_par_iter()
-        .zip(mut_slices)
-        .for_each(|(input, mut out)| {
-            parse_line_to_tiles::<16>(input, &mut out);
-        });
-
-    let (start, _) = full_grid.par_iter().enumerate().find_first(|(_, t)| **t == Tile::Start).unwrap();
-
-    let pipe_start = 'pipe_start: {
-        let start_north = start - ROW_SIZE;
-        match full_grid[start_north] {
-            Tile::NorthSouth | Tile::SouthWest | Tile::SouthEast => {
-                break 'pipe_start start_north;
-            },
-            _ => {}
-        };
-
-        let start_south = start + ROW_SIZE;
-        match full_grid[start_south] {
-            Tile::NorthSouth | Tile::NorthWest | Tile::NorthEast => {
-                break 'pipe_start start_south;
-            },
-            _ => {}
-        };
-
-        // there have to be 2 entrances, so...
-        let start_east = start - 1;
-        // let start_west = start + 1;
-        start_east
-    };
+/// Sum of the distances between every pair of coordinates,
+/// using sorted coordinates so each one is only visited once.
+fn sum_pairwise(sorted: &[usize]) -> usize {
+    let mut total = 0;
+    let mut preceding = 0;
+    for (idx, coord) in sorted.iter().enumerate() {
+        total += (coord * idx) - preceding;
+        preceding += coord;
+    }
+    total
+}
 
-    // println!("start at {} {}", start / ROW_SIZE, start % ROW_SIZE);
-
-    // for i in 0..GRID_ROWS {
-    //     println!("{:?}", &full_grid[i..(i*ROW_SIZE)]);
-    // }
-
-    let mut pos = pipe_start;
-    let mut last_pos = start;
-    let mut length = 1;
-    loop {
-        // println!(
-        //     "now at {} {} tile {:?}", 
-        //     pos / ROW_SIZE, 
-        //     pos % ROW_SIZE,
-        //     full_grid[pos],
-        // );
-        let new_pos = match full_grid[pos] {
-            Tile::Ground => panic!("ran aground!"),
-            Tile::NorthSouth => match last_pos < pos {
-                true => pos + ROW_SIZE, // we were north, go south
-                false => pos - ROW_SIZE, // we were south, go north
-            },
-            Tile::EastWest => match last_pos < pos {
-                true => pos + 1, // we were west, go east
-                false => pos - 1, // we were east, go west
-            },
-            Tile::NorthEast => match last_pos < pos {
-                true => pos + 1, // we were north, go east
-                false => pos - ROW_SIZE, // we were east, go north
-            },
-            Tile::NorthWest => match last_pos == pos - 1 {
-                true => pos - ROW_SIZE, // we were west, go north
-                false => pos - 1, // we were north, go west
-            },
-            Tile::SouthWest => match last_pos < pos {
-                true => pos + ROW_SIZE, // we were west, go south
-                false => pos - 1, // we were south, go west
-            },
-            Tile::SouthEast => match last_pos == pos + 1 {
-                true => pos + ROW_SIZE, // we were east, go south
-                false => pos + 1, // we were south, go east
-            },
-            Tile::Start => {break;},
-        };
-        last_pos = pos;
-        pos = new_pos;
-        length += 1;
+pub(crate) fn sum_distances(grid: &Grid<Star>, expansion: usize) -> usize {
+    // a row is empty if a lanewise-bitwise OR finds no stars
+    let empty_rows: Vec<bool> = grid
+        .padded_rows()
+        .map(|row| row.chunks_exact(CHUNK).all(|c| u8x16::from_slice_aligned(c).or() == 0))
+        .collect();
+
+    // a column is empty if no row has a star in it
+    let row_size = grid.row_size();
+    let mut occupied_cols = vec![u8x16::splat(0); row_size / CHUNK];
+    for row in grid.padded_rows() {
+        for (acc, slice) in occupied_cols.iter_mut().zip(row.chunks_exact(CHUNK)) {
+            *acc |= u8x16::from_slice_aligned(slice);
+        }
+    }
+    let mut empty_cols = vec![false; row_size];
+    for (chunk, out) in occupied_cols.iter().zip(empty_cols.chunks_exact_mut(CHUNK)) {
+        let chunk = <[u8; CHUNK]>::from(*chunk);
+        for idx in 0..CHUNK {
+            out[idx] = chunk[idx] != STAR;
+        }
     }
-    length / 2
+
+    // work out how far each row and column moves once space expands
+    let expand = |empty: &[bool]| -> Vec<usize> {
+        empty
+            .iter()
+            .scan(0, |gaps, is_empty| {
+                let pos = *gaps;
+                if *is_empty { *gaps += expansion - 1 }
+                Some(pos)
+            })
+            .collect()
+    };
+    let row_offsets = expand(&empty_rows);
+    let col_offsets = expand(&empty_cols);
+
+    let (ys, mut xs): (Vec<usize>, Vec<usize>) = grid
+        .as_slice()
+        .par_iter()
+        .enumerate()
+        .filter(|(_, s)| **s == STAR)
+        .map(|(idx, _)| {
+            let (x, y) = grid.coord_of(idx);
+            (y + row_offsets[y], x + col_offsets[x])
+        })
+        .unzip();
+
+    // stars are found row by row, so only the columns need sorting
+    xs.par_sort_unstable();
+
+    sum_pairwise(&ys) + sum_pairwise(&xs)
+}
+
+pub fn total_distance(grid: &Grid<Star>) -> usize {
+    sum_distances(grid, EXPANSION)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_parse_line() {
-        let input = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n";
-        let actual = parse_lines(input.par_lines());
+        let input = "...#......\n.......#..\n#.........\n..........\n......#...\n.#........\n.........#\n..........\n.......#..\n#...#.....\n";
+        let actual = total_distance(&parse_grid(input).unwrap());
+
+        assert_eq!(actual, 374);
 
-        assert_eq!(actual, 8);
-        
     }
 
+    #[test]
+    fn rows_wider_than_a_chunk() {
+        // the example widened to 19 columns, with one more galaxy past the first chunk
+        let input = "...#...........#...\n.......#...........\n#..................\n...................\n......#............\n.#.................\n.........#.........\n...................\n.......#...........\n#...#..............\n";
+        let actual = total_distance(&parse_grid(input).unwrap());
+
+        assert_eq!(actual, 586);
+    }
 }
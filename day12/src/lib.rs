@@ -0,0 +1,28 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use rayon::prelude::*;
+
+pub mod part_one;
+pub mod part_two;
+pub mod spring;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed<'a> = Vec<spring::Row>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        spring::parse_rows(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(rows: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(rows.par_iter().map(part_one::arrangements).sum())
+    }
+
+    fn part_two(rows: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(rows.par_iter().map(part_two::arrangements).sum())
+    }
+}
@@ -1,40 +1,6 @@
 use itertools::Itertools;
-use nom::{
-    branch::alt, 
-    bytes::complete::tag, 
-    character::complete::u8,
-    combinator::value, 
-    IResult, 
-    multi::{separated_list1, many1}, 
-    sequence::separated_pair, Finish, 
-};
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Spring {
-    Operational, // .
-    Damaged, // #
-    Unknown // ?
-}
-
-fn parse_spring(input: &str) -> IResult<&str, Spring> {
-    alt((
-        value(Spring::Operational, tag(".")),
-        value(Spring::Damaged, tag("#")),
-        value(Spring::Unknown, tag("?"))
-    ))(input)
-}
-
-
-fn parse_line(input: &str) -> IResult<&str, (Vec<Spring>, Vec<u8>)> {
-    separated_pair(
-        many1(parse_spring), 
-        tag(" "), 
-        separated_list1(
-            tag(","), 
-            u8
-        )
-    )(input)
-}
+use crate::spring::{Row, Spring};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Validation {
@@ -99,7 +65,7 @@ fn validate_combination(springs: &[Spring], counts: &[u8], damaged: &[usize]) ->
     Validation::Passed
 }
 
-fn calc(springs: Vec<Spring>, counts: Vec<u8>) -> usize {
+fn calc(springs: &[Spring], counts: &[u8]) -> usize {
     // algorithm
     //
     // work out target number of springs?
@@ -116,31 +82,20 @@ fn calc(springs: Vec<Spring>, counts: Vec<u8>) -> usize {
                 _ => None,
             })
         .combinations(target - current)
-        .filter(|combination| validate_combination(&springs, &counts, &combination) == Validation::Passed)
+        .filter(|combination| validate_combination(springs, counts, &combination) == Validation::Passed)
         .count()
 }
 
-pub fn parse_and_calc_line(input: &str) -> usize {
-    let (_, (springs, counts)) = parse_line(input).finish().unwrap();
-    calc(springs, counts)
+/// How many ways the row's unknown springs could be filled in
+pub fn arrangements(row: &Row) -> usize {
+    calc(&row.springs, &row.counts)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spring::parse_row;
 
-    #[test]
-    fn test_parse() {
-        let input = "?#?#?..#?#? 1,3,1,6";
-        let (_, (springs, counts)) = parse_line(input).unwrap();
-        assert_eq!(springs, vec![
-            Spring::Unknown, Spring::Damaged, Spring::Unknown, Spring::Damaged, Spring::Unknown, 
-            Spring::Operational, Spring::Operational,
-            Spring::Damaged, Spring::Unknown, Spring::Damaged, Spring::Unknown,
-            ]);
-        assert_eq!(counts, vec![1, 3, 1, 6]);
-    }
-    
     #[test]
     fn test_parse_and_calc_line() {
         let inputs = [
@@ -152,9 +107,8 @@ mod tests {
             ("?###???????? 3,2,1", 10),
         ];
         for (line, expected) in inputs {
-            let actual = parse_and_calc_line(line);
+            let actual = arrangements(&parse_row(line).unwrap());
             assert_eq!(actual, expected);
         };
     }
-
 }
@@ -0,0 +1,78 @@
+use nom::{
+    branch::alt, 
+    bytes::complete::tag, 
+    character::complete::u8,
+    combinator::{all_consuming, value}, 
+    error::Error,
+    IResult, 
+    multi::{separated_list1, many1}, 
+    sequence::separated_pair, Finish, 
+};
+use rayon::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Spring {
+    Operational, // .
+    Damaged, // #
+    Unknown // ?
+}
+
+fn parse_spring(input: &str) -> IResult<&str, Spring> {
+    alt((
+        value(Spring::Operational, tag(".")),
+        value(Spring::Damaged, tag("#")),
+        value(Spring::Unknown, tag("?"))
+    ))(input)
+}
+
+
+fn parse_line(input: &str) -> IResult<&str, (Vec<Spring>, Vec<u8>)> {
+    separated_pair(
+        many1(parse_spring), 
+        tag(" "), 
+        separated_list1(
+            tag(","), 
+            u8
+        )
+    )(input)
+}
+
+/// A row of springs, and the sizes of its contiguous groups of damaged ones
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Row {
+    pub springs: Vec<Spring>,
+    pub counts: Vec<u8>,
+}
+
+pub fn parse_row(input: &str) -> Result<Row, Error<&str>> {
+    let (_, (springs, counts)) = all_consuming(parse_line)(input).finish()?;
+    Ok(Row { springs, counts })
+}
+
+/// Every row, in order
+pub fn parse_rows(input: &str) -> Result<Vec<Row>, Error<&str>> {
+    input.par_lines().map(parse_row).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "?#?#?..#?#? 1,3,1,6";
+        let row = parse_row(input).unwrap();
+        assert_eq!(row.springs, vec![
+            Spring::Unknown, Spring::Damaged, Spring::Unknown, Spring::Damaged, Spring::Unknown, 
+            Spring::Operational, Spring::Operational,
+            Spring::Damaged, Spring::Unknown, Spring::Damaged, Spring::Unknown,
+            ]);
+        assert_eq!(row.counts, vec![1, 3, 1, 6]);
+    }
+
+    #[test]
+    fn unknown_spring() {
+        let actual = parse_row("??x.### 1,1,3");
+        assert_eq!(actual, Err(Error::new("x.### 1,1,3", nom::error::ErrorKind::Tag)));
+    }
+}
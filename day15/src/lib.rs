@@ -0,0 +1,28 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use rayon::prelude::*;
+
+pub mod part_one;
+pub mod part_two;
+pub mod step;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Parsed<'a> = Vec<step::Step<'a>>;
+    type PartOne = u32;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        step::parse_steps(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(steps: &Self::Parsed<'_>) -> Result<u32> {
+        Ok(steps.par_iter().map(|step| part_one::hash_step(step.text)).sum())
+    }
+
+    fn part_two(steps: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_two::process_steps(steps))
+    }
+}
@@ -1,8 +1,9 @@
-use rayon::{prelude::*, str::Split};
+use rayon::prelude::*;
+
+use crate::step::{Lens, Operation, Step};
 
 type Hash = usize;
 type Label<'a> = &'a [u8];
-type Lens = usize;
 
 fn hash_label(label: Label) -> Hash {
     let mut val: u8 = 0;
@@ -18,24 +19,17 @@ enum Instruction<'a> {
     Add(Hash, Label<'a>, Lens)
 }
 
-pub fn process_steps(steps: Split<'_, char>) -> usize {
+pub fn process_steps(steps: &[Step]) -> usize {
     let mut hashmap: Vec<Vec<(Label, Lens)>> = vec![Vec::with_capacity(8); 256];
 
     let instructions: Vec<Instruction> = steps
-        .into_par_iter()
+        .par_iter()
         .map(|step| {
-            let bytes = step.as_bytes();
-            let (last, rest) = bytes.split_last().unwrap();
+            let label = step.label.as_bytes();
 
-            match last {
-                b'-' => {
-                    Instruction::Remove(hash_label(rest), rest)
-                },
-                _ => {
-                    let (_, label) = rest.split_last().unwrap();
-                    let focal_length = (last - 48) as usize; // ascii 0-9 is 48 to 57
-                    Instruction::Add(hash_label(label), label, focal_length)
-                }
+            match step.operation {
+                Operation::Remove => Instruction::Remove(hash_label(label), label),
+                Operation::Insert(focal_length) => Instruction::Add(hash_label(label), label, focal_length),
             }
         })
         .collect();
@@ -77,6 +71,7 @@ pub fn process_steps(steps: Split<'_, char>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::step::parse_steps;
     
     #[test]
     fn test_parse_line() {
@@ -101,4 +96,10 @@ mod tests {
         
     }
 
+    #[test]
+    fn focusing_power() {
+        let steps = parse_steps("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n").unwrap();
+        assert_eq!(process_steps(&steps), 145);
+    }
+
 }
@@ -0,0 +1,67 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, u8},
+    combinator::{all_consuming, map, value},
+    error::Error,
+    Finish,
+    IResult,
+    sequence::{pair, preceded},
+};
+use rayon::prelude::*;
+
+pub type Lens = usize;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Operation {
+    /// `-`
+    Remove,
+    /// `=` and a focal length
+    Insert(Lens),
+}
+
+/// One step of the initialization sequence
+#[derive(PartialEq, Eq, Debug)]
+pub struct Step<'a> {
+    /// The whole step as written, which part one hashes
+    pub text: &'a str,
+    pub label: &'a str,
+    pub operation: Operation,
+}
+
+fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    alt((
+        value(Operation::Remove, tag("-")),
+        map(preceded(tag("="), u8), |lens| Operation::Insert(lens as Lens)),
+    ))(input)
+}
+
+pub fn parse_step(text: &str) -> Result<Step, Error<&str>> {
+    let (_, (label, operation)) = all_consuming(pair(alpha1, parse_operation))(text).finish()?;
+    Ok(Step { text, label, operation })
+}
+
+/// Every comma separated step, ignoring the newline at the end
+pub fn parse_steps(input: &str) -> Result<Vec<Step>, Error<&str>> {
+    input.trim_end().par_split(',').map(parse_step).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_from_input() {
+        let steps = parse_steps("rn=1,cm-\n").unwrap();
+        assert_eq!(steps, vec![
+            Step { text: "rn=1", label: "rn", operation: Operation::Insert(1) },
+            Step { text: "cm-", label: "cm", operation: Operation::Remove },
+        ]);
+    }
+
+    #[test]
+    fn missing_operation() {
+        let steps = parse_steps("rn=1,cm,qp=3");
+        assert_eq!(steps, Err(Error::new("", nom::error::ErrorKind::Tag)));
+    }
+}
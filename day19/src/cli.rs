@@ -1,13 +1,17 @@
 use clap::{command, Parser};
+use common::Output;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// File to parse
-    pub path: String,
+    /// Files to parse, `-` for stdin
+    #[arg(required = true)]
+    pub paths: Vec<String>,
     /// Part two
     #[arg(long)]
     pub part_two: bool,
+    #[command(flatten)]
+    pub output: Output,
 }
 
 pub fn parse() -> Cli {
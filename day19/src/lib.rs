@@ -0,0 +1,27 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+
+pub mod part_one;
+pub mod part_two;
+pub mod system;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Parsed<'a> = system::System<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        system::parse_system(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(system: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_one::process_parts(system))
+    }
+
+    fn part_two(system: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_two::accepted_combinations(system))
+    }
+}
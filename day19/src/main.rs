@@ -1,29 +1,17 @@
-use std::{fs, time::Instant};
 use anyhow::Result;
-use rayon::prelude::*;
+use common::{Part, Solution};
+use day19::Day19;
 
 mod cli;
-mod part_one;
-mod part_two;
 
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
 
-    let timer = Instant::now();
-    let sum: usize = match &args.part_two {
-        false => {
-            let (flows, parts) = part_one::parse_lines(lines);
-            part_one::process_parts(flows, parts)
-        },
-        true => {
-            part_two::parse_lines(lines)
-        },
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    let base_time = timer.elapsed();
-    
-    println!("Value is: {} calculated in {}", sum, base_time.as_micros());
-    Ok(()) 
+
+    args.output.print_each(&args.paths, |input| Day19::run(part, input))
 }
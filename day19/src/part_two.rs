@@ -1,8 +1,7 @@
 use std::collections::HashMap;
 
 use petgraph::{graph::{Graph, NodeIndex, EdgeReference}, visit::EdgeRef};
-use rayon::{prelude::*, str::Lines};
-use crate::part_one::{parse_workflow, Workflow, Step};
+use crate::system::{Label, System, Workflow, Step};
 
 enum Comparison {
     LessThan(u16),
@@ -27,14 +26,14 @@ enum Edge {
 impl From<&Step<'_>> for Node {
     fn from(value: &Step) -> Self {
         let comp = match value.comp {
-            crate::part_one::Comparison::LessThan => Comparison::LessThan(value.val),
-            crate::part_one::Comparison::GreaterThan => Comparison::GreaterThan(value.val),
+            crate::system::Comparison::LessThan => Comparison::LessThan(value.val),
+            crate::system::Comparison::GreaterThan => Comparison::GreaterThan(value.val),
         };
         match value.category {
-            crate::part_one::Category::X => Node::X(comp),
-            crate::part_one::Category::M => Node::M(comp),
-            crate::part_one::Category::A => Node::A(comp),
-            crate::part_one::Category::S => Node::S(comp),
+            crate::system::Category::X => Node::X(comp),
+            crate::system::Category::M => Node::M(comp),
+            crate::system::Category::A => Node::A(comp),
+            crate::system::Category::S => Node::S(comp),
         }
     }
 }
@@ -42,7 +41,7 @@ impl From<&Step<'_>> for Node {
 type FlowLabel<'a> = &'a str;
 
 fn flow_onto_graph(
-    workflow: Workflow, 
+    workflow: &Workflow, 
     entrypoint: NodeIndex, 
     graph: &mut Graph<Node, Edge>,
     entrypoints: &HashMap<FlowLabel, NodeIndex>,
@@ -58,9 +57,9 @@ fn flow_onto_graph(
 
         // Create edge for condition met 
         let condition_passed_node = match step.dest {
-            crate::part_one::Label::Rejected => reject,
-            crate::part_one::Label::Accepted => accept,
-            crate::part_one::Label::Flow(lbl) => entrypoints[lbl],
+            Label::Rejected => reject,
+            Label::Accepted => accept,
+            Label::Flow(lbl) => entrypoints[lbl],
         };
         graph.add_edge(condition_passed_node, entrypoint, Edge::FromPass);
 
@@ -94,9 +93,9 @@ fn flow_onto_graph(
 
         // Create edge for condition met for current node
         let condition_met_node = match step.dest {
-            crate::part_one::Label::Rejected => reject,
-            crate::part_one::Label::Accepted => accept,
-            crate::part_one::Label::Flow(lbl) => entrypoints[lbl],
+            Label::Rejected => reject,
+            Label::Accepted => accept,
+            Label::Flow(lbl) => entrypoints[lbl],
         };
         graph.add_edge(condition_met_node, node, Edge::FromPass);
     }
@@ -104,9 +103,9 @@ fn flow_onto_graph(
     // handle fail condition for last step
     {
         let condition_failed_node = match workflow.dest {
-            crate::part_one::Label::Rejected => reject,
-            crate::part_one::Label::Accepted => accept,
-            crate::part_one::Label::Flow(lbl) => entrypoints[lbl],
+            Label::Rejected => reject,
+            Label::Accepted => accept,
+            Label::Flow(lbl) => entrypoints[lbl],
         };
         graph.add_edge(condition_failed_node, prev_node, Edge::FromFail);
     }
@@ -126,18 +125,18 @@ struct Bounds {
     s: Range
 }
 
-pub fn parse_lines(lines: Lines) -> usize {
-    let mut workflows: HashMap<FlowLabel, Workflow> = lines
-        .into_par_iter()
-        .filter(|l| *l != "" && !l.starts_with("{"))
-        .filter_map(|line| {
-            let (label, flow) = parse_workflow(line).unwrap().1;
+/// How many combinations of ratings would be accepted
+pub fn accepted_combinations(system: &System) -> usize {
+    let mut workflows: HashMap<FlowLabel, &Workflow> = system
+        .workflows
+        .iter()
+        .filter_map(|(label, flow)| {
             match label {
                 // these won't really happen
-                crate::part_one::Label::Rejected => None,
-                crate::part_one::Label::Accepted => None,
+                Label::Rejected => None,
+                Label::Accepted => None,
                 // this is the only actual result of parsing
-                crate::part_one::Label::Flow(lbl) => Some((lbl, flow)),
+                Label::Flow(lbl) => Some((*lbl, flow)),
             }
         })
         .collect();
@@ -184,8 +183,6 @@ pub fn parse_lines(lines: Lines) -> usize {
         walk(&graph, &mut accumulator, bounds, edge);
     }
 
-    println!("{:?}", accumulator);
-    
     overlapped_hyperrectangles(accumulator)
     
 }
@@ -282,34 +279,7 @@ fn overlapped_hyperrectangles(hyperrects: Vec<Bounds>) -> usize {
     ])
     .collect();
 
-    println!("len space {}", partitioned_space.len());
-
-    let vols = generate_volumes_from_partitioned_space(partitioned_space);
-
-    // println!("{:#?}", vols);
-
-    println!("len vols {}", vols.len());
-
-    for (idx, vol) in vols.iter().enumerate() {
-        let num = idx + 1;
-
-        println!(
-            "1_s_{}_a_{}_m_{}_x_{}     {}     {} {}",
-            vol.id.s.unwrap_or(999),
-            vol.id.a.unwrap_or(999),
-            vol.id.m.unwrap_or(999),
-            vol.id.x.unwrap_or(999),
-            vol.min,
-            vol.max, 
-            match vol.dimension {
-                Dimension::X => 'x',
-                Dimension::M => 'm',
-                Dimension::A => 'a',
-                Dimension::S => 's',
-            }
-        )
-    }
-
+    let _vols = generate_volumes_from_partitioned_space(partitioned_space);
 
     0
 }
@@ -322,6 +292,8 @@ struct VolumeIdentifier {
     s: Option<u16>,
 }
 
+// not read until the overlaps are counted from them
+#[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 struct ExpandedVolume {
     id: VolumeIdentifier,
@@ -418,6 +390,7 @@ fn generate_volumes_from_partitioned_space(space: Vec<(Dimension, u16)>) -> Vec<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::system::parse_system;
 
     #[test]
     fn test_overlapped_hypervolumes() {
@@ -447,7 +420,7 @@ gd{a>3333:R,R}
 hdj{m>838:A,pv}
 ";
         
-        let sum = parse_lines(input.par_lines());
+        let sum = accepted_combinations(&parse_system(input).unwrap());
         assert_eq!(sum, 167_409_079_868_000);
         
     }
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, u16},
+    combinator::{all_consuming, value, map},
+    error::Error,
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+    Finish,
+    IResult,
+};
+use rayon::{iter::Either, prelude::*};
+
+#[derive(Debug)]
+pub struct Part {
+    pub x: u16,
+    pub m: u16,
+    pub a: u16,
+    pub s: u16,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Label<'a> {
+    Rejected,
+    Accepted,
+    Flow(&'a str)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    LessThan,
+    GreaterThan
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Step<'a> {
+    pub category: Category,
+    pub comp: Comparison,
+    pub val: u16,
+    pub dest: Label<'a>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Workflow<'a> {
+    pub steps: Vec<Step<'a>>,
+    pub dest: Label<'a>,
+}
+
+fn parse_label(input: &str) -> IResult<&str, Label> {
+    alt((
+        value(Label::Rejected, tag("R")),
+        value(Label::Accepted, tag("A")),
+        map(alpha1, |flow| Label::Flow(flow))
+    ))(input)
+}
+
+fn parse_category(input: &str) -> IResult<&str, Category> {
+    alt((
+        value(Category::X, tag("x")),
+        value(Category::M, tag("m")),
+        value(Category::A, tag("a")),
+        value(Category::S, tag("s")),
+    ))(input)
+}
+
+fn parse_step(input: &str) -> IResult<&str, Step> {
+    let (r, (category, comp, val, dest)) = tuple((
+        parse_category,
+        alt((value(Comparison::LessThan, tag("<")), value(Comparison::GreaterThan, tag(">")))),
+        u16,
+        preceded(tag(":"), parse_label),
+    ))(input)?;
+
+    Ok((
+        r,
+        Step {
+            category,
+            comp,
+            val,
+            dest,
+        },
+    ))
+}
+
+pub fn parse_workflow(input: &str) -> IResult<&str, (Label, Workflow)> {
+    let (r, (lbl, steps, dest)) = tuple((
+        parse_label,
+        preceded(tag("{"), separated_list1(tag(","), parse_step)),
+        delimited(tag(","), parse_label, tag("}")),
+    ))(input)?;
+
+    Ok((r, (lbl, Workflow { steps, dest })))
+}
+
+fn parse_part(input: &str) -> IResult<&str, Part> {
+    let (r, (x, m, a, s)) = tuple((
+        preceded(tag("{x="), u16),
+        preceded(tag(",m="), u16),
+        preceded(tag(",a="), u16),
+        delimited(tag(",s="), u16, tag("}")),
+    ))(input)?;
+
+    Ok((r, Part { x, m, a, s }))
+}
+
+/// The workflows by name, and the parts to run through them
+#[derive(Debug)]
+pub struct System<'a> {
+    pub workflows: HashMap<Label<'a>, Workflow<'a>>,
+    pub parts: Vec<Part>,
+}
+
+pub fn parse_system(input: &str) -> Result<System, Error<&str>> {
+    let parsed = input.par_lines().filter(|l| !l.is_empty()).map(|line| {
+        if line.starts_with('{') {
+            all_consuming(parse_part)(line).finish().map(|(_, part)| Either::Right(part))
+        } else {
+            all_consuming(parse_workflow)(line).finish().map(|(_, flow)| Either::Left(flow))
+        }
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    let (workflows, parts) = parsed.into_par_iter().partition_map(|either| either);
+    Ok(System { workflows, parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workflow() {
+        let inputs = [
+            (
+                "px{a<2006:qkq,m>2090:A,rfg}",
+                Workflow { 
+                    steps: vec![
+                        Step { category: Category::A, comp: Comparison::LessThan, val: 2006, dest: Label::Flow("qkq") },
+                        Step { category: Category::M, comp: Comparison::GreaterThan, val: 2090, dest: Label::Accepted }
+                    ], 
+                    dest: Label::Flow("rfg")
+                }
+            ),
+            (
+                "pv{a>1716:R,A}",
+                Workflow { 
+                    steps: vec![
+                        Step { category: Category::A, comp: Comparison::GreaterThan, val: 1716, dest: Label::Rejected }
+                    ], 
+                    dest: Label::Accepted
+                }
+        ),
+            (
+                "lnx{m>1548:A,A}",
+                Workflow { 
+                    steps: vec![
+                        Step { category: Category::M, comp: Comparison::GreaterThan, val: 1548, dest: Label::Accepted }
+                    ], 
+                    dest: Label::Accepted
+                }
+            ),
+            (
+                "rfg{s<537:gd,x>2440:R,A}",
+                Workflow { 
+                    steps: vec![
+                        Step { category: Category::S, comp: Comparison::LessThan, val: 537, dest: Label::Flow("gd") },
+                        Step { category: Category::X, comp: Comparison::GreaterThan, val: 2440, dest: Label::Rejected }
+                    ], 
+                    dest: Label::Accepted
+                }
+            ),
+        ];
+        for (input, expected) in inputs {
+            let (_, actual) = parse_workflow(input).unwrap().1;
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn unterminated_part() {
+        let input = "in{s<1351:A,R}\n\n{x=787,m=2655,a=1222,s=2876\n";
+        let actual = parse_system(input).map(|_| ());
+        assert_eq!(actual, Err(Error::new("", nom::error::ErrorKind::Tag)));
+    }
+}
@@ -1,13 +1,39 @@
 use clap::{command, Parser};
+use common::Output;
+use day2::{game::DiceCount, limits::parse_limit};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// File to parse
-    pub path: String,
+    /// Files to parse, `-` for stdin
+    #[arg(required = true)]
+    pub paths: Vec<String>,
     /// Part two
     #[arg(long)]
     pub part_two: bool,
+    /// How many cubes of a colour the bag holds, as `colour=count`,
+    /// replacing the puzzle's 12 red, 13 green and 14 blue
+    #[arg(long, value_parser = parse_limit)]
+    pub limit: Vec<(String, DiceCount)>,
+    /// File of `colour = count` lines, read before any `--limit`
+    #[arg(long)]
+    pub limits: Option<String>,
+    /// List why each game was or wasn't possible and the smallest bag for it,
+    /// as a table or with `--format json` one object per game, rather than answering
+    #[arg(long)]
+    pub report: bool,
+    /// Print the chance of each game's rounds being drawn from a bag holding exactly the limits,
+    /// drawing each round's cubes at once and putting them back afterwards
+    #[arg(long, conflicts_with = "report")]
+    pub likelihood: bool,
+    /// Print the bag making the games most likely, and the chance of each game with it
+    #[arg(long, conflicts_with_all = ["report", "likelihood"])]
+    pub estimate: bool,
+    /// Most cubes of one colour `--estimate` considers
+    #[arg(long, default_value_t = 50)]
+    pub max_cubes: DiceCount,
+    #[command(flatten)]
+    pub output: Output,
 }
 
 pub fn parse() -> Cli {
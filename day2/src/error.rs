@@ -0,0 +1,96 @@
+//! Parse errors that know where they happened and what should have been there.
+use std::fmt;
+use nom::{error::{ErrorKind, ParseError}, IResult};
+use nom_locate::LocatedSpan;
+
+/// A line being parsed, tracking the column reached
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// What the grammar was looking for when it gave up
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Expected {
+    Text(&'static str),
+    Number,
+    Colour,
+    /// Another cube, another round, or nothing
+    More,
+    Other(ErrorKind),
+}
+
+impl Expected {
+    /// Described for a person, `colours` being the ones worth suggesting
+    pub fn describe(&self, colours: &[&str]) -> String {
+        match self {
+            Expected::Text(text) => format!("{:?}", text),
+            Expected::Number => "a number".to_string(),
+            Expected::Colour if colours.is_empty() => "a colour".to_string(),
+            Expected::Colour => format!("one of {}", colours.join(", ")),
+            Expected::More => r#"one of ", ", "; " or the end of the line"#.to_string(),
+            Expected::Other(kind) => kind.description().to_string(),
+        }
+    }
+}
+
+/// The nom error for day2's grammar
+#[derive(PartialEq, Debug)]
+pub struct SpanError<'a> {
+    pub at: Span<'a>,
+    pub expected: Expected,
+}
+
+impl<'a> ParseError<Span<'a>> for SpanError<'a> {
+    fn from_error_kind(input: Span<'a>, kind: ErrorKind) -> Self {
+        SpanError { at: input, expected: Expected::Other(kind) }
+    }
+
+    /// The innermost error says the most about what went wrong
+    fn append(_: Span<'a>, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+/// Run `parser`, saying it expected `expected` if it fails
+/// and nothing inside it has said more precisely.
+pub fn expect<'a, O>(
+    expected: Expected,
+    mut parser: impl FnMut(Span<'a>) -> IResult<Span<'a>, O, SpanError<'a>>,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, O, SpanError<'a>> {
+    move |input| {
+        parser(input).map_err(|e| e.map(|err| match err.expected {
+            Expected::Other(_) => SpanError { at: err.at, expected: expected.clone() },
+            _ => err,
+        }))
+    }
+}
+
+/// A game that failed to parse, for showing to a person
+#[derive(PartialEq, Eq, Debug)]
+pub struct GameError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    pub expected: String,
+    pub text: String,
+}
+
+impl GameError {
+    pub fn new(line: usize, text: &str, err: SpanError, colours: &[&str]) -> Self {
+        GameError {
+            line,
+            column: err.at.get_utf8_column(),
+            expected: err.expected.describe(colours),
+            text: text.trim_end_matches('\r').to_string(),
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "line {}, column {}: expected {}", self.line, self.column, self.expected)?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for GameError {}
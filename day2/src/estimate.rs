@@ -0,0 +1,243 @@
+//! How likely a bag makes the games, and the bag that makes them most likely.
+//!
+//! Each round is a handful drawn from the bag without replacement,
+//! every cube going back before the next round, so the chance of a round is
+//! multivariate hypergeometric: with `N` cubes in the bag, `N_c` of colour `c`,
+//! drawing `n_c` of each colour, `k` in all, happens with probability
+//! `product over c of C(N_c, n_c) / C(N, k)`.
+//! Every game is drawn from the same bag.
+use std::collections::BTreeMap;
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+use serde::{Serialize, Serializer};
+
+use crate::game::{Colour, DiceCount, Game, GameID, Round};
+
+/// How many cubes of each colour are in the bag
+pub type Bag<'a> = BTreeMap<Colour<'a>, DiceCount>;
+
+/// `ln(n!)` for every `n` up to the size of the largest bag considered
+struct LnFactorials(Vec<f64>);
+
+impl LnFactorials {
+    fn up_to(n: usize) -> Self {
+        let mut table = Vec::with_capacity(n + 1);
+        table.push(0.0);
+        for i in 1..=n {
+            table.push(table[i - 1] + (i as f64).ln());
+        }
+        LnFactorials(table)
+    }
+
+    fn ln_choose(&self, n: usize, k: usize) -> f64 {
+        match k <= n {
+            true => self.0[n] - self.0[k] - self.0[n - k],
+            false => f64::NEG_INFINITY,
+        }
+    }
+}
+
+fn round_log_likelihood(bag: &Bag, total: usize, round: &Round, table: &LnFactorials) -> f64 {
+    let mut drawn = 0;
+    let mut ln = 0.0;
+    for &(count, colour) in &round.cubes {
+        let count = usize::from(count);
+        let in_bag = usize::from(bag.get(colour).copied().unwrap_or(0));
+        // otherwise it's -inf less -inf, which isn't a number
+        if count > in_bag {
+            return f64::NEG_INFINITY;
+        }
+        ln += table.ln_choose(in_bag, count);
+        drawn += count;
+    }
+    match drawn <= total {
+        true => ln - table.ln_choose(total, drawn),
+        false => f64::NEG_INFINITY,
+    }
+}
+
+fn bag_size(bag: &Bag) -> usize {
+    bag.values().map(|&n| usize::from(n)).sum()
+}
+
+fn game_log_likelihood(bag: &Bag, game: &Game, table: &LnFactorials) -> f64 {
+    let total = bag_size(bag);
+    game.rounds.iter().map(|round| round_log_likelihood(bag, total, round, table)).sum()
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct GameLikelihood {
+    pub id: GameID,
+    pub probability: f64,
+    #[serde(serialize_with = "log")]
+    pub log_likelihood: f64,
+}
+
+/// JSON has no infinities, so an impossible game's is written `"-inf"` rather than `null`
+fn log<S: Serializer>(ln: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    match ln.is_finite() {
+        true => serializer.serialize_f64(*ln),
+        false => serializer.serialize_str(&ln.to_string()),
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct Likelihood<'a> {
+    pub bag: Bag<'a>,
+    /// Of every game at once
+    #[serde(serialize_with = "log")]
+    pub log_likelihood: f64,
+    pub games: Vec<GameLikelihood>,
+}
+
+/// The chance of each game's rounds, and of them all, drawing from `bag`.
+pub fn likelihood<'a>(bag: Bag<'a>, games: &[Game]) -> Likelihood<'a> {
+    let table = LnFactorials::up_to(bag_size(&bag));
+    let games: Vec<GameLikelihood> = games
+        .par_iter()
+        .map(|game| {
+            let log_likelihood = game_log_likelihood(&bag, game, &table);
+            GameLikelihood { id: game.id, probability: log_likelihood.exp(), log_likelihood }
+        })
+        .collect();
+    let log_likelihood = games.iter().map(|g| g.log_likelihood).sum();
+    Likelihood { bag, log_likelihood, games }
+}
+
+/// The smallest bag every game could have been drawn from
+fn smallest_bag<'a>(games: &[Game<'a>]) -> Bag<'a> {
+    games.iter().fold(Bag::new(), |mut bag, game| {
+        for (colour, max) in game.maxima() {
+            let n = bag.entry(colour).or_default();
+            *n = max.max(*n);
+        }
+        bag
+    })
+}
+
+/// The bag making `games` most likely, with at most `max_per_colour` cubes of any colour.
+///
+/// Climbs from the smallest possible bag, adding or removing a cube of one colour,
+/// or swapping a cube of one colour for another, while that makes the games more likely.
+/// The likelihood can keep rising as the bag grows, leaving the estimate at the cap.
+/// Fails if the games show more cubes of a colour than the cap allows.
+pub fn estimate<'a>(games: &[Game<'a>], max_per_colour: DiceCount) -> Result<Likelihood<'a>> {
+    let floor = smallest_bag(games);
+    if let Some((colour, &most)) = floor.iter().find(|(_, &n)| n > max_per_colour) {
+        bail!("a game shows {} {} cubes, more than the {} allowed", most, colour, max_per_colour);
+    }
+    let table = LnFactorials::up_to(floor.len() * usize::from(max_per_colour.max(1)));
+    let score = |bag: &Bag| -> f64 {
+        games.par_iter().map(|game| game_log_likelihood(bag, game, &table)).sum()
+    };
+
+    let colours: Vec<Colour> = floor.keys().copied().collect();
+    let mut bag = floor.clone();
+    let mut best = score(&bag);
+    loop {
+        let mut moves: Vec<Vec<(Colour, i32)>> = Vec::new();
+        for &c in &colours {
+            moves.push(vec![(c, 1)]);
+            moves.push(vec![(c, -1)]);
+            for &other in &colours {
+                if other != c {
+                    moves.push(vec![(c, 1), (other, -1)]);
+                }
+            }
+        }
+
+        let candidates = moves.iter().filter_map(|changes| {
+            let mut next = bag.clone();
+            for &(colour, change) in changes {
+                let n = next.get_mut(colour)?;
+                let moved = i32::from(*n) + change;
+                if moved < i32::from(floor[colour]) || moved > i32::from(max_per_colour) {
+                    return None;
+                }
+                *n = moved as DiceCount;
+            }
+            Some(next)
+        });
+        let Some((next, ln)) = candidates
+            .map(|next| {
+                let ln = score(&next);
+                (next, ln)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            break;
+        };
+
+        // stop once no step is a real improvement, rather than wander about a plateau
+        if ln <= best + 1e-9 {
+            break;
+        }
+        bag = next;
+        best = ln;
+    }
+    Ok(likelihood(bag, games))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_game;
+
+    fn games(lines: &[&'static str]) -> Vec<Game<'static>> {
+        lines.iter().map(|line| parse_game(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn chance_of_each_game() {
+        let games = games(&["Game 1: 1 red, 1 blue; 2 red", "Game 2: 2 blue"]);
+        let bag = Bag::from([("red", 2), ("blue", 1)]);
+        let result = likelihood(bag, &games);
+
+        // 2 of the 3 pairs are a red and the blue, 1 of 3 is both reds
+        assert!((result.games[0].probability - 2.0 / 9.0).abs() < 1e-12);
+        // there is only the one blue
+        assert_eq!(result.games[1].probability, 0.0);
+        assert_eq!(result.log_likelihood, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn most_likely_bag() {
+        let games = games(&[
+            "Game 1: 2 red, 1 blue; 3 red; 1 green, 1 red",
+            "Game 2: 1 blue, 1 green; 2 red, 1 green; 4 red",
+            "Game 3: 1 red; 1 red, 1 blue, 1 green",
+        ]);
+        let cap = 12;
+        let estimate = estimate(&games, cap).unwrap();
+
+        // every bag under the cap, to be sure the climb didn't stop on a lesser peak
+        let mut best = f64::NEG_INFINITY;
+        for red in 4..=cap {
+            for blue in 1..=cap {
+                for green in 1..=cap {
+                    let bag = Bag::from([("red", red), ("blue", blue), ("green", green)]);
+                    best = best.max(likelihood(bag, &games).log_likelihood);
+                }
+            }
+        }
+        assert!((estimate.log_likelihood - best).abs() < 1e-9, "{:?} isn't the best, {}", estimate.bag, best);
+        assert!(estimate.bag["red"] > estimate.bag["blue"]);
+    }
+
+    #[test]
+    fn more_drawn_than_in_the_bag() {
+        let games = games(&["Game 1: 2 red", "Game 2: 1 red, 1 blue"]);
+        let result = likelihood(Bag::from([("red", 1)]), &games);
+        assert_eq!(result.games[0].probability, 0.0);
+        assert_eq!(result.games[1].log_likelihood, f64::NEG_INFINITY);
+        assert_eq!(result.log_likelihood, f64::NEG_INFINITY);
+        assert!(serde_json::to_string(&result).unwrap().starts_with("{\"bag\":{\"red\":1},\"log_likelihood\":\"-inf\""));
+    }
+
+    #[test]
+    fn cap_below_what_was_seen() {
+        let games = games(&["Game 1: 5 red, 5 blue"]);
+        let error = estimate(&games, 2).unwrap_err();
+        assert_eq!(error.to_string(), "a game shows 5 blue cubes, more than the 2 allowed");
+    }
+}
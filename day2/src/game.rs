@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use nom::{
+    bytes::complete::{tag, take_while1},
+    IResult,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, pair},
+    combinator::{all_consuming, cut, map},
+    Finish,
+};
+use rayon::prelude::*;
+
+use crate::error::{expect, Expected, GameError, Span, SpanError};
+
+pub type GameID = u16;
+pub type DiceCount = u16;
+/// Any word will do as a colour
+pub type Colour<'a> = &'a str;
+
+/// Whether `c` can be part of a colour's name, in games and in `--limit` alike
+pub fn is_colour_char(c: char) -> bool {
+    c.is_alphabetic()
+}
+
+/// The cubes shown in one handful, in the order listed
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Round<'a> {
+    pub cubes: Vec<(DiceCount, Colour<'a>)>,
+}
+
+impl Round<'_> {
+    /// How many cubes of `colour` were shown, none if it wasn't listed
+    pub fn count(&self, colour: &str) -> DiceCount {
+        self.cubes
+            .iter()
+            .filter(|(_, c)| *c == colour)
+            .map(|(count, _)| count)
+            .sum()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Game<'a> {
+    pub id: GameID,
+    pub rounds: Vec<Round<'a>>,
+}
+
+/// The most cubes of each colour shown in any one round, for the colours shown at all
+pub type GameMaxima<'a> = BTreeMap<Colour<'a>, DiceCount>;
+
+impl<'a> Game<'a> {
+    pub fn maxima(&self) -> GameMaxima<'a> {
+        let counts = self.rounds.iter().flat_map(|round| &round.cubes);
+        counts.fold(
+            GameMaxima::new(),
+            |mut acc, &(count, colour)| {
+                let max = acc.entry(colour).or_default();
+                *max = count.max(*max);
+                acc
+            }
+        )
+    }
+}
+
+fn parse_game_id(input: Span) -> IResult<Span, GameID, SpanError> {
+    delimited(
+        expect(Expected::Text("Game "), tag("Game ")),
+        expect(Expected::Number, nom::character::complete::u16),
+        expect(Expected::Text(": "), tag(": "))
+    )(input)
+}
+
+fn parse_colour(input: Span) -> IResult<Span, Colour, SpanError> {
+    map(
+        expect(Expected::Colour, take_while1(is_colour_char)),
+        |colour: Span| *colour.fragment()
+    )(input)
+}
+
+fn parse_one_dice_number(input: Span) -> IResult<Span, (DiceCount, Colour), SpanError> {
+    separated_pair(
+        expect(Expected::Number, nom::character::complete::u16),
+        expect(Expected::Text(" "), tag(" ")),
+        parse_colour,
+    )(input)
+}
+
+/// Once past a separator there has to be another cube,
+/// so failing after one is cut short rather than ending the list
+fn parse_one_round(input: Span) -> IResult<Span, Round, SpanError> {
+    map(
+        separated_list1(tag(", "), cut(parse_one_dice_number)),
+        |cubes| Round { cubes }
+    )(input)
+}
+
+fn parse_all_rounds(input: Span) -> IResult<Span, Vec<Round>, SpanError> {
+    separated_list1(
+        tag("; "),
+        cut(parse_one_round)
+    )(input)
+}
+
+pub fn parse_game(line: &str) -> Result<Game, SpanError> {
+    let (_, (id, rounds)) = expect(Expected::More, all_consuming(pair(
+        parse_game_id,
+        parse_all_rounds
+    )))(Span::new(line)).finish()?;
+    Ok(Game { id, rounds })
+}
+
+/// Parse every game in `input`, carrying on past any that fail.
+/// `colours` are suggested when a colour is missing.
+pub fn parse_games<'a>(input: &'a str, colours: &[&str]) -> (Vec<Game<'a>>, Vec<GameError>) {
+    let lines: Vec<&str> = input.lines().collect();
+    let parsed: Vec<_> = lines.par_iter().map(|line| parse_game(line)).collect();
+
+    let mut games = Vec::with_capacity(parsed.len());
+    let mut errors = Vec::new();
+    for (i, game) in parsed.into_iter().enumerate() {
+        match game {
+            Ok(game) => games.push(game),
+            Err(err) => errors.push(GameError::new(i + 1, lines[i], err, colours)),
+        }
+    }
+    (games, errors)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_round_is_kept() {
+        let game = parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red").unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(game.rounds[1].cubes, vec![(5, "blue"), (4, "red"), (13, "green")]);
+        assert_eq!(game.rounds[2].count("blue"), 0);
+        assert_eq!(game.maxima(), GameMaxima::from([("red", 20), ("green", 13), ("blue", 6)]));
+    }
+
+    #[test]
+    fn errors_say_what_was_expected() {
+        let colours = ["red", "green", "blue"];
+        let expected = |line| {
+            let err = parse_game(line).unwrap_err();
+            (err.at.get_utf8_column(), err.expected.describe(&colours))
+        };
+        assert_eq!(expected("Game 6: 3 , 4 red"), (11, "one of red, green, blue".to_string()));
+        assert_eq!(expected("Game 6: 3 red, 4"), (17, r#"" ""#.to_string()));
+        assert_eq!(expected("Game 6: 3 red,  4 blue"), (16, "a number".to_string()));
+        assert_eq!(expected("Game 6: 3 red 4 blue"), (14, r#"one of ", ", "; " or the end of the line"#.to_string()));
+        assert_eq!(expected("Gaem 6: 3 red"), (1, r#""Game ""#.to_string()));
+    }
+
+    #[test]
+    fn later_games_parse_after_a_failure() {
+        let input = "Game 1: 3 blue\nGame 2: 4 3 red\nGame 3: 1 red\nGame 4 2 red\n";
+        let (games, errors) = parse_games(input, &["red", "green", "blue"]);
+
+        assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "line 2, column 11: expected one of red, green, blue\n  Game 2: 4 3 red\n            ^"
+        );
+        assert_eq!(errors[1].line, 4);
+        assert_eq!(errors[1].expected, r#"": ""#);
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::{bail, Result};
+use common::Solution;
+use rayon::prelude::*;
+
+pub mod error;
+pub mod estimate;
+pub mod game;
+pub mod limits;
+pub mod part_one;
+pub mod part_two;
+pub mod report;
+
+use game::Game;
+use limits::Limits;
+
+pub struct Day2;
+
+/// Parse every game, failing if any can't be, with the colours of `limits`
+/// suggested when a game is missing one.
+pub fn parse_with<'a>(input: &'a str, limits: &Limits) -> Result<Vec<Game<'a>>> {
+    let (games, errors) = game::parse_games(input, &limits.colours().collect::<Vec<_>>());
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        bail!("{} of {} games failed to parse\n{}", errors.len(), games.len() + errors.len(), errors.join("\n"));
+    }
+    Ok(games)
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Parsed<'a> = Vec<Game<'a>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        parse_with(input, &Limits::default())
+    }
+
+    fn part_one(games: &Self::Parsed<'_>) -> Result<usize> {
+        let limits = Limits::default();
+        Ok(games.par_iter().map(|game| part_one::game_value(game, &limits)).sum())
+    }
+
+    fn part_two(games: &Self::Parsed<'_>) -> Result<usize> {
+        let limits = Limits::default();
+        Ok(games.par_iter().map(|game| part_two::power(game, &limits)).sum())
+    }
+}
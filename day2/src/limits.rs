@@ -0,0 +1,132 @@
+//! How many cubes of each colour the bag holds.
+use std::{fs, path::Path};
+use anyhow::{anyhow, Context, Result};
+
+use crate::{estimate::Bag, game::{is_colour_char, DiceCount, GameMaxima}};
+
+/// Each colour's limit, in the order the colours were first given
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Limits(Vec<(String, DiceCount)>);
+
+impl Default for Limits {
+    /// The puzzle's bag
+    fn default() -> Self {
+        Limits::from_iter([("red", 12), ("green", 13), ("blue", 14)].map(|(c, n)| (c.to_string(), n)))
+    }
+}
+
+impl FromIterator<(String, DiceCount)> for Limits {
+    fn from_iter<I: IntoIterator<Item = (String, DiceCount)>>(limits: I) -> Self {
+        let mut all = Limits(Vec::new());
+        for (colour, n) in limits {
+            all.set(colour, n);
+        }
+        all
+    }
+}
+
+/// Parse a `colour=count` pair, as given to `--limit`
+pub fn parse_limit(limit: &str) -> Result<(String, DiceCount)> {
+    let (colour, count) = limit
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected colour=count, not {}", limit))?;
+    let colour = colour.trim();
+    if colour.is_empty() || !colour.chars().all(is_colour_char) {
+        return Err(anyhow!("{} isn't a colour", colour));
+    }
+    let count = count.trim().parse().with_context(|| format!("{} isn't a count of cubes", count.trim()))?;
+    Ok((colour.to_string(), count))
+}
+
+impl Limits {
+    /// Read limits from a file of `colour = count` lines,
+    /// skipping blank lines and those starting with `#`.
+    pub fn load(path: &Path) -> Result<Limits> {
+        let file = fs::read_to_string(path)
+            .with_context(|| format!("reading limits {}", path.display()))?;
+        file.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(n, line)| parse_limit(line).with_context(|| format!("line {} of {}", n + 1, path.display())))
+            .collect()
+    }
+
+    /// Limits from `--limits` then `--limit`, the later winning,
+    /// or the puzzle's own if neither is given.
+    pub fn from_args(file: Option<&str>, limits: &[(String, DiceCount)]) -> Result<Limits> {
+        if file.is_none() && limits.is_empty() {
+            return Ok(Limits::default());
+        }
+        let mut all = match file {
+            Some(path) => Limits::load(Path::new(path))?,
+            None => Limits(Vec::new()),
+        };
+        for (colour, n) in limits {
+            all.set(colour.clone(), *n);
+        }
+        Ok(all)
+    }
+
+    /// Limit `colour` to `n`, keeping its place if it already has a limit
+    fn set(&mut self, colour: String, n: DiceCount) {
+        match self.0.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, limit)) => *limit = n,
+            None => self.0.push((colour, n)),
+        }
+    }
+
+    /// At most how many cubes of `colour` there are, none if the bag has no limit for it
+    pub fn get(&self, colour: &str) -> DiceCount {
+        self.0.iter().find(|(c, _)| c == colour).map_or(0, |&(_, n)| n)
+    }
+
+    /// The limits as a bag holding exactly that many of each colour
+    pub fn as_bag(&self) -> Bag<'_> {
+        self.0.iter().map(|(colour, n)| (colour.as_str(), *n)).collect()
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(colour, _)| colour.as_str())
+    }
+
+    /// Whether the bag holds at least as many cubes of each colour as were shown
+    pub fn allow(&self, maxima: &GameMaxima) -> bool {
+        maxima.iter().all(|(colour, max)| *max <= self.get(colour))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_from_args() {
+        assert_eq!(parse_limit("purple=3").unwrap(), ("purple".to_string(), 3));
+        assert_eq!(parse_limit(" red = 12 ").unwrap(), ("red".to_string(), 12));
+        assert_eq!(parse_limit("red").unwrap_err().to_string(), "expected colour=count, not red");
+        assert!(parse_limit("red=lots").is_err());
+
+        let limits = Limits::from_args(None, &[("purple".to_string(), 3)]).unwrap();
+        assert_eq!(limits.colours().collect::<Vec<_>>(), vec!["purple"]);
+        assert_eq!(limits.get("red"), 0);
+        assert_eq!(Limits::from_args(None, &[]).unwrap(), Limits::default());
+    }
+
+    #[test]
+    fn colours_keep_their_order() {
+        assert_eq!(Limits::default().colours().collect::<Vec<_>>(), vec!["red", "green", "blue"]);
+
+        let limits = Limits::from_iter([("red", 12), ("green", 13), ("blue", 14), ("red", 3)].map(|(c, n)| (c.to_string(), n)));
+        assert_eq!(limits.colours().collect::<Vec<_>>(), vec!["red", "green", "blue"]);
+        assert_eq!(limits.get("red"), 3);
+    }
+
+    #[test]
+    fn colours_beyond_ascii() {
+        let limits = Limits::from_args(None, &[parse_limit("grün=3").unwrap()]).unwrap();
+        let games = crate::parse_with("Game 1: 2 grün\nGame 2: 4 grün", &limits).unwrap();
+        let values: Vec<_> = games.iter().map(|game| crate::part_one::game_value(game, &limits)).collect();
+        assert_eq!(values, vec![1, 0]);
+        assert_eq!(parse_limit("grün!=3").unwrap_err().to_string(), "grün! isn't a colour");
+    }
+}
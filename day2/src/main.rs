@@ -1,23 +1,106 @@
-use std::fs;
-use rayon::prelude::*;
 use anyhow::Result;
+use common::{read_input, Format, Output, Part, Report, Solution};
+use day2::{
+    estimate::{self, Likelihood},
+    game::{self, Game},
+    limits::Limits,
+    part_one, part_two, report, Day2,
+};
+use rayon::prelude::*;
 
 mod cli;
-mod part_one;
-mod part_two;
 
+/// Read and parse each input in turn, naming it first if there are several and the output is text.
+/// Games that fail to parse are reported and left out, rather than stopping everything.
+fn for_each_input(
+    paths: &[String],
+    output: &Output,
+    limits: &Limits,
+    mut each: impl FnMut(&[Game]) -> Result<()>,
+) -> Result<()> {
+    let colours: Vec<&str> = limits.colours().collect();
+    for path in paths {
+        let input = read_input(path)?;
+        let (games, errors) = game::parse_games(&input, &colours);
+        for error in errors {
+            eprintln!("{}: {}", path, error);
+        }
+        if paths.len() > 1 && output.format == Format::Text {
+            println!("{}:", path);
+        }
+        each(&games)?;
+    }
+    Ok(())
+}
+
+fn print_reports(games: &[Game], output: &Output, limits: &Limits) -> Result<()> {
+    match output.format {
+        Format::Text => {
+            println!("{}", report::HEADER);
+            for game in games {
+                println!("{}", report::report(game, limits));
+            }
+        }
+        Format::Json => {
+            for game in games {
+                println!("{}", serde_json::to_string(&report::report(game, limits))?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_likelihood(likelihood: &Likelihood, output: &Output) -> Result<()> {
+    match output.format {
+        Format::Text => {
+            let bag: Vec<String> = likelihood.bag.iter().map(|(colour, n)| format!("{} {}", n, colour)).collect();
+            println!("bag: {}", bag.join(", "));
+            println!("log likelihood: {:.4}", likelihood.log_likelihood);
+            println!(" game  probability");
+            for game in &likelihood.games {
+                println!("{:>5}  {:.4e}", game.id, game.probability);
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string(likelihood)?),
+    }
+    Ok(())
+}
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
 
-    let parse_line = match &args.part_two {
-        false => part_one::parse_line,
-        true => part_two::parse_line,
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    
-    let sum: usize = lines.map(parse_line).sum();
-    println!("Value is: {}", sum);
-    Ok(()) 
+
+    let limits = Limits::from_args(args.limits.as_deref(), &args.limit)?;
+    let output = &args.output;
+    if args.report {
+        return for_each_input(&args.paths, output, &limits, |games| print_reports(games, output, &limits));
+    }
+    if args.likelihood {
+        return for_each_input(&args.paths, output, &limits, |games| {
+            print_likelihood(&estimate::likelihood(limits.as_bag(), games), output)
+        });
+    }
+    if args.estimate {
+        return for_each_input(&args.paths, output, &limits, |games| {
+            print_likelihood(&estimate::estimate(games, args.max_cubes)?, output)
+        });
+    }
+    if limits == Limits::default() {
+        return args.output.print_each(&args.paths, |input| Day2::run(part, input));
+    }
+    args.output.answer_file.refuse_variant("--limit or --limits")?;
+
+    args.output.print_each(&args.paths, |input| {
+        Report::measure(Day2::DAY, part, || day2::parse_with(input, &limits), |games| {
+            let score = match part {
+                Part::One => part_one::game_value,
+                Part::Two => part_two::power,
+            };
+            Ok(games.par_iter().map(|game| score(game, &limits)).sum::<usize>().to_string())
+        })
+    })
 }
@@ -1,118 +1,23 @@
-use nom::{
-    branch::alt, 
-    bytes::complete::tag, 
-    IResult, 
-    multi::separated_list1,
-    sequence::{delimited, separated_pair, pair}, 
-    combinator::{value, all_consuming}, 
-};
+use crate::{error::SpanError, game::{parse_game, Game}, limits::Limits};
 
-type GameID = u16;
 
-fn parse_game_id(input: &str) -> IResult<&str, GameID> {
-    delimited(
-        tag("Game "),
-        nom::character::complete::u16,
-        tag(": ")
-    )(input)
-}
-
-
-#[derive(Clone)]
-enum Colour {
-    Green,
-    Blue,
-    Red
-}
-
-fn parse_colour(input: &str) -> IResult<&str, Colour> {
-    alt((
-        value(Colour::Red, tag("red")),
-        value(Colour::Green, tag("green")),
-        value(Colour::Blue, tag("blue"))
-    ))(input)
-}
-
-type DiceCount = u16;
-
-fn parse_one_dice_number(input: &str) -> IResult<&str, (DiceCount, Colour)> {
-    separated_pair(
-        nom::character::complete::u16, 
-        tag(" "), 
-        parse_colour,
-    )(input)
-}
-
-type Round = Vec<(DiceCount, Colour)>;
-
-fn parse_one_round(input: &str) -> IResult<&str, Round> {
-    separated_list1(
-        tag(", "), 
-        parse_one_dice_number
-    )(input)
-}
-
-#[derive(Debug)]
-struct GameMaxima {
-    red: DiceCount,
-    green: DiceCount,
-    blue: DiceCount
-}
-
-fn parse_all_rounds(input: &str) -> IResult<&str, GameMaxima> {
-    let (remainder, rounds) = separated_list1(
-        tag("; "),
-        parse_one_round
-    )(input)?;
-
-    let counts = rounds.into_iter().flatten();
-    let maxima = counts.fold(
-        GameMaxima { red: 0, green: 0, blue: 0 },
-        |mut acc, (count, colour)| {
-            match colour {
-                Colour::Green => { acc.green = count.max(acc.green); },
-                Colour::Blue => { acc.blue = count.max(acc.blue); },
-                Colour::Red => { acc.red = count.max(acc.red); },
-            };
-            acc
-        }
-    );
-    Ok((remainder, maxima))
-}
-
-type Game = (GameID, GameMaxima);
-
-fn parse_game(input: &str) -> IResult<&str, Game> {
-    all_consuming(pair(
-        parse_game_id, 
-        parse_all_rounds
-    ))(input)
+/// The game's id if the bag could have held every round, otherwise nothing
+pub fn game_value(game: &Game, limits: &Limits) -> usize {
+    match limits.allow(&game.maxima()) {
+        true => game.id.into(),
+        false => 0,
+    }
 }
 
-const MAX_RED: u16 = 12;
-const MAX_GREEN: u16 = 13;
-const MAX_BLUE: u16 = 14;
-
-
-pub fn parse_line(line: &str) -> usize {
-    let res = parse_game(line);
-    if let Ok((_, (game_id, maxima))) = res {
-        if maxima.red <= MAX_RED && maxima.green <= MAX_GREEN && maxima.blue <= MAX_BLUE {
-            game_id.into()
-        } else {
-            0
-        }
-    } else {
-        println!("something went wrong");
-        println!("{:#?}", res);
-        0
-    }
+pub fn parse_line(line: &str) -> Result<usize, SpanError> {
+    parse_game(line).map(|game| game_value(&game, &Limits::default()))
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Expected;
         
     #[test]
     fn game_id_value_from_line() {
@@ -125,7 +30,26 @@ mod tests {
         ];
         for (line, expectation) in lines {
             let result = parse_line(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
+
+    #[test]
+    fn unknown_colour() {
+        // parses, but there are no bleu cubes in the bag
+        let result = parse_line("Game 6: 3 bleu, 4 red");
+        assert_eq!(result, Ok(0));
+
+        let err = parse_line("Game 6: 3 , 4 red").unwrap_err();
+        assert_eq!((err.at.get_utf8_column(), err.expected), (11, Expected::Colour));
+    }
+
+    #[test]
+    fn other_colours() {
+        let game = parse_game("Game 7: 2 purple, 4 red; 3 purple").unwrap();
+        let limits = Limits::from_iter([("purple".to_string(), 3), ("red".to_string(), 4)]);
+        assert_eq!(game_value(&game, &limits), 7);
+        let limits = Limits::from_iter([("purple".to_string(), 2), ("red".to_string(), 4)]);
+        assert_eq!(game_value(&game, &limits), 0);
+    }
 }
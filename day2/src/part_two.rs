@@ -1,100 +1,21 @@
-use nom::{
-    branch::alt, 
-    bytes::complete::tag, 
-    IResult, 
-    multi::separated_list1,
-    sequence::{delimited, separated_pair, preceded}, 
-    combinator::{value, all_consuming}, character::complete::digit1, 
-};
+use crate::{error::SpanError, game::{parse_game, Game}, limits::Limits};
 
-fn parse_game_id(input: &str) -> IResult<&str, &str> {
-    delimited(
-        tag("Game "),
-        digit1,
-        tag(": ")
-    )(input)
+/// The product of the fewest cubes of each colour the game could be played with,
+/// over the colours the bag has limits for and any others the game shows.
+/// A colour in the bag that the game never shows needs none of its cubes,
+/// so the power is 0.
+pub fn power(game: &Game, limits: &Limits) -> usize {
+    let maxima = game.maxima();
+    let unlisted = maxima.keys().filter(|colour| !limits.colours().any(|c| c == **colour));
+    limits
+        .colours()
+        .chain(unlisted.copied())
+        .map(|colour| usize::from(maxima.get(colour).copied().unwrap_or(0)))
+        .product()
 }
 
-
-#[derive(Clone)]
-enum Colour {
-    Green,
-    Blue,
-    Red
-}
-
-fn parse_colour(input: &str) -> IResult<&str, Colour> {
-    alt((
-        value(Colour::Red, tag("red")),
-        value(Colour::Green, tag("green")),
-        value(Colour::Blue, tag("blue"))
-    ))(input)
-}
-
-type DiceCount = u16;
-
-fn parse_one_dice_number(input: &str) -> IResult<&str, (DiceCount, Colour)> {
-    separated_pair(
-        nom::character::complete::u16, 
-        tag(" "), 
-        parse_colour,
-    )(input)
-}
-
-type Round = Vec<(DiceCount, Colour)>;
-
-fn parse_one_round(input: &str) -> IResult<&str, Round> {
-    separated_list1(
-        tag(", "), 
-        parse_one_dice_number
-    )(input)
-}
-
-#[derive(Debug)]
-struct GameMaxima {
-    red: DiceCount,
-    green: DiceCount,
-    blue: DiceCount
-}
-
-fn parse_all_rounds(input: &str) -> IResult<&str, GameMaxima> {
-    let (remainder, rounds) = separated_list1(
-        tag("; "),
-        parse_one_round
-    )(input)?;
-
-    let counts = rounds.into_iter().flatten();
-    let maxima = counts.fold(
-        GameMaxima { red: 0, green: 0, blue: 0 },
-        |mut acc, (count, colour)| {
-            match colour {
-                Colour::Green => { acc.green = count.max(acc.green); },
-                Colour::Blue => { acc.blue = count.max(acc.blue); },
-                Colour::Red => { acc.red = count.max(acc.red); },
-            };
-            acc
-        }
-    );
-    Ok((remainder, maxima))
-}
-
-
-fn parse_game(input: &str) -> IResult<&str, GameMaxima> {
-    all_consuming(preceded(
-        parse_game_id, 
-        parse_all_rounds
-    ))(input)
-}
-
-pub fn parse_line(line: &str) -> usize {
-    let res = parse_game(line);
-    if let Ok((_, maxima)) = res {
-        usize::from(maxima.red) * usize::from(maxima.green) * usize::from(maxima.blue)
-    } else {
-        println!("something went wrong");
-        println!("{:#?}", res);
-        0
-    }
+pub fn parse_line(line: &str) -> Result<usize, SpanError> {
+    parse_game(line).map(|game| power(&game, &Limits::default()))
 }
 
 
@@ -113,7 +34,17 @@ mod tests {
         ];
         for (line, expectation) in lines {
             let result = parse_line(line);
-            assert_eq!(result, expectation);
+            assert_eq!(result, Ok(expectation));
         }
     }
+
+    #[test]
+    fn power_of_other_colours() {
+        let game = parse_game("Game 1: 2 purple, 4 red; 3 purple, 1 green").unwrap();
+        let limits = Limits::from_iter([("purple".to_string(), 9)]);
+        // 3 purple, 4 red and 1 green
+        assert_eq!(power(&game, &limits), 12);
+        // no blue shown, so a bag with blue in it has a power of zero
+        assert_eq!(power(&game, &Limits::default()), 0);
+    }
 }
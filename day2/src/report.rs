@@ -0,0 +1,113 @@
+//! Why each game was or wasn't possible, and the smallest bag it could be played with.
+use std::fmt;
+use serde::Serialize;
+
+use crate::{game::{DiceCount, Game, GameID, GameMaxima}, limits::Limits, part_two::power};
+
+/// A colour shown in greater numbers than the bag holds
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct Excess<'a> {
+    /// Counting rounds from 1
+    pub round: usize,
+    pub colour: &'a str,
+    pub shown: DiceCount,
+    pub limit: DiceCount,
+}
+
+impl Excess<'_> {
+    pub fn over(&self) -> DiceCount {
+        self.shown - self.limit
+    }
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct GameReport<'a> {
+    pub id: GameID,
+    pub possible: bool,
+    pub excesses: Vec<Excess<'a>>,
+    pub minimal_bag: GameMaxima<'a>,
+    pub power: usize,
+}
+
+pub fn report<'a>(game: &Game<'a>, limits: &Limits) -> GameReport<'a> {
+    let excesses: Vec<Excess> = game
+        .rounds
+        .iter()
+        .enumerate()
+        .flat_map(|(i, round)| {
+            round.cubes.iter().filter_map(move |&(shown, colour)| {
+                let limit = limits.get(colour);
+                (shown > limit).then_some(Excess { round: i + 1, colour, shown, limit })
+            })
+        })
+        .collect();
+
+    GameReport {
+        id: game.id,
+        possible: excesses.is_empty(),
+        excesses,
+        minimal_bag: game.maxima(),
+        power: power(game, limits),
+    }
+}
+
+/// The header for [`GameReport`]'s table rows
+pub const HEADER: &str = " game  possible  minimal bag                     power  exceeded";
+
+impl fmt::Display for GameReport<'_> {
+    /// One row of a table
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bag: Vec<String> = self.minimal_bag.iter().map(|(colour, n)| format!("{} {}", n, colour)).collect();
+        let excesses: Vec<String> = self
+            .excesses
+            .iter()
+            .map(|e| format!("round {} {} {} > {} by {}", e.round, e.colour, e.shown, e.limit, e.over()))
+            .collect();
+        let row = format!(
+            "{:>5}  {:<8}  {:<28}  {:>7}  {}",
+            self.id,
+            if self.possible { "yes" } else { "no" },
+            bag.join(", "),
+            self.power,
+            excesses.join("; ")
+        );
+        f.write_str(row.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_game;
+
+    #[test]
+    fn excesses_by_round() {
+        let limits = Limits::default();
+        let game = parse_game("Game 4: 1 green, 3 red, 6 blue; 3 green, 16 red; 3 green, 15 blue, 14 red").unwrap();
+        let report = report(&game, &limits);
+
+        assert!(!report.possible);
+        assert_eq!(report.excesses, vec![
+            Excess { round: 2, colour: "red", shown: 16, limit: 12 },
+            Excess { round: 3, colour: "blue", shown: 15, limit: 14 },
+            Excess { round: 3, colour: "red", shown: 14, limit: 12 },
+        ]);
+        assert_eq!(report.power, 3 * 16 * 15);
+        assert_eq!(
+            report.to_string(),
+            "    4  no        15 blue, 3 green, 16 red          720  \
+             round 2 red 16 > 12 by 4; round 3 blue 15 > 14 by 1; round 3 red 14 > 12 by 2"
+        );
+    }
+
+    #[test]
+    fn report_as_json() {
+        let game = parse_game("Game 1: 3 blue, 4 red; 2 green").unwrap();
+        let json = serde_json::to_string(&report(&game, &Limits::default())).unwrap();
+        assert_eq!(report(&game, &Limits::default()).to_string(), "    1  yes       3 blue, 2 green, 4 red             24");
+        assert_eq!(
+            json,
+            r#"{"id":1,"possible":true,"excesses":[],"minimal_bag":{"blue":3,"green":2,"red":4},"power":24}"#
+        );
+    }
+}
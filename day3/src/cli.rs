@@ -1,13 +1,47 @@
-use clap::{command, Parser};
+use clap::{command, Parser, ValueEnum};
+use common::Output;
+use day3::rules::{parse_rule, Rule};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// File to parse
-    pub path: String,
+    /// Files to parse, `-` for stdin
+    #[arg(required = true)]
+    pub paths: Vec<String>,
     /// Part two
     #[arg(long)]
     pub part_two: bool,
+    /// The character marking an empty cell
+    #[arg(long, default_value_t = '.')]
+    pub blank: char,
+    /// Exactly which characters are symbols, rather than ASCII punctuation
+    #[arg(long)]
+    pub symbols: Option<String>,
+    /// Count any character that isn't blank, a digit or whitespace as a symbol
+    #[arg(long, conflicts_with = "symbols")]
+    pub unicode: bool,
+    /// Answer by a rule rather than either part, as `symbol:neighbours:aggregate`,
+    /// e.g. `*:2:product` or `#:any:sum`; several rules add up.
+    /// `product` and `max` combine each symbol's numbers on their own, while `sum`
+    /// adds every picked number once, however many picked symbols it touches
+    #[arg(long, value_parser = parse_rule, conflicts_with = "part_two")]
+    pub rule: Vec<Rule>,
+    /// Draw the schematic with part numbers, other numbers and gears picked out,
+    /// rather than answering
+    #[arg(long, value_enum, conflicts_with_all = ["part_two", "rule"])]
+    pub render: Option<View>,
+    #[command(flatten)]
+    pub output: Output,
+}
+
+#[derive(ValueEnum, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum View {
+    /// Coloured for a terminal
+    Ansi,
+    /// A web page
+    Html,
+    /// An image
+    Svg,
 }
 
 pub fn parse() -> Cli {
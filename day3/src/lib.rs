@@ -0,0 +1,53 @@
+use anyhow::Result;
+use common::{grid::Grid, Solution};
+
+pub mod part_one;
+pub mod part_two;
+pub mod render;
+pub mod rules;
+pub mod schematic;
+pub mod symbols;
+
+use schematic::Schematic;
+use symbols::Classifier;
+
+/// The puzzle's example schematic
+#[cfg(test)]
+const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+/// Index the schematic in `input`, its symbols being those `classifier` says
+pub fn parse_with(input: &str, classifier: &Classifier) -> Result<Schematic> {
+    let grid = Grid::parse_chars(input, classifier.blank, |c| c)?;
+    schematic::index(&grid, classifier)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Parsed<'a> = Schematic;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        parse_with(input, &Classifier::default())
+    }
+
+    fn part_one(schematic: &Self::Parsed<'_>) -> Result<usize> {
+        part_one::part_number_sum(schematic)
+    }
+
+    fn part_two(schematic: &Self::Parsed<'_>) -> Result<usize> {
+        part_two::gear_shift(schematic)
+    }
+}
@@ -1,39 +1,64 @@
-use std::fs;
-use rayon::prelude::*;
 use anyhow::Result;
-
+use cli::View;
+use common::{grid::Grid, read_input, Part, Report, Solution};
+use day3::{parse_with, render, rules, schematic, symbols::{Classifier, Symbols}, Day3};
 
 mod cli;
-mod part_one;
-mod part_two;
 
+fn classifier(args: &cli::Cli) -> Classifier {
+    let symbols = match (&args.symbols, args.unicode) {
+        (Some(set), _) => Symbols::Set(set.chars().collect()),
+        (None, true) => Symbols::Unicode,
+        (None, false) => Symbols::Punctuation,
+    };
+    Classifier { blank: args.blank, symbols }
+}
+
+fn print_render(paths: &[String], classifier: &Classifier, view: View) -> Result<()> {
+    for path in paths {
+        let input = read_input(path)?;
+        let grid = Grid::parse_chars(&input, classifier.blank, |c| c)?;
+        let cells = render::annotate(&grid, &schematic::index(&grid, classifier)?)?;
+        let drawn = match view {
+            View::Ansi => render::ansi(&grid, &cells),
+            View::Html => render::html(&grid, &cells),
+            View::Svg => render::svg(&grid, &cells),
+        };
+        print!("{}", drawn);
+    }
+    Ok(())
+}
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    match args.part_two {
-        false => {
-            let chars: Vec<char> = file
-                .par_lines()
-                .map(|l| l.par_chars())
-                .flatten()
-                .collect();
 
-            let grid = chars.try_into().unwrap();
-            
-            let sum: usize = part_one::search_grid(grid);
-            println!("Value is: {}", sum);
-        }
-        true => {
-            let chars: part_two::Grid = file
-                .par_lines()
-                .map(|l| l.par_chars().collect())
-                .collect();
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
+    };
 
-            let val = part_two::gear_shift(chars);
-            println!("Value is: {}", val);
-        }
+    let classifier = classifier(&args);
+    if let Some(view) = args.render {
+        return print_render(&args.paths, &classifier, view);
+    }
+    if !args.rule.is_empty() {
+        args.output.answer_file.refuse_variant("--rule")?;
+        return args.output.print_each(&args.paths, |input| {
+            Report::measure(Day3::DAY, part, || parse_with(input, &classifier), |schematic| {
+                Ok(rules::apply_all(&args.rule, schematic)?.to_string())
+            })
+            .map(Report::to_neither_part)
+        });
     }
-    
-    Ok(()) 
+    if classifier == Classifier::default() {
+        return args.output.print_each(&args.paths, |input| Day3::run(part, input));
+    }
+    args.output.answer_file.refuse_variant("--blank, --symbols or --unicode")?;
+
+    args.output.print_each(&args.paths, |input| {
+        Report::measure(Day3::DAY, part, || parse_with(input, &classifier), |schematic| match part {
+            Part::One => Day3::part_one(schematic).map(|answer| answer.to_string()),
+            Part::Two => Day3::part_two(schematic).map(|answer| answer.to_string()),
+        })
+    })
 }
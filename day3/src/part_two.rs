@@ -1,79 +1,50 @@
-use rayon::prelude::*;
+use anyhow::Result;
 
-pub type Grid = Vec<Vec<char>>;
+use crate::{rules::PART_TWO, schematic::Schematic};
 
-struct Coord {
-    chr: char,
-    x: usize,
-    y: usize
+/// The sum of each gear's ratio, a gear being a `*` touching exactly two numbers,
+/// so there are none unless the schematic's classifier counts `*` as a symbol
+pub fn gear_shift(schematic: &Schematic) -> Result<usize> {
+    PART_TWO.apply(schematic)
 }
 
-enum C {
-    Digit(Coord),
-    Gear(Coord)
-} 
-
-pub fn gear_shift(grid: Grid) -> usize {
-    let max_size = grid.len();
-
-    let (digits, gears): (Vec<_>, Vec<_>) = grid
-        .par_iter()
-        .enumerate()
-        .flat_map(|(x, row)| {
-            row
-                .into_par_iter()
-                .enumerate()
-                .filter_map(move |(y, chr)| match chr {
-                    '0'..='9' => Some(C::Digit(Coord { chr: *chr, x, y })),
-                    '*' => Some(C::Gear(Coord { chr: *chr, x, y })),
-                    _ => None,
-                })
-        })
-        .partition_map(|c| match c {
-            C::Digit(c) => rayon::iter::Either::Left(c),
-            C::Gear(g) => rayon::iter::Either::Right(g),
-        });
-
-    gears
-        .into_par_iter()
-        .fold(
-            || 0 as usize, 
-            |acc, gear| {
-            // after 
-            let y = gear.y + 1;
-            if y < max_size && grid[gear.x][y].is_numeric() {
-                for n in y..(max_size.min(y + 2)) {}
-            }
-            acc + 1
-        })
-        .sum()
-    
-}
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
-        
+    use common::grid::Grid;
+    use crate::{schematic::index, symbols::{Classifier, Symbols}, EXAMPLE};
+
     #[test]
-    fn sum_part_numbers() {
+    fn sum_gear_ratios() {
+        let grid = Grid::parse_chars(EXAMPLE, '.', |c| c).unwrap();
+
+        let result = gear_shift(&index(&grid, &Classifier::default()).unwrap()).unwrap();
+        assert_eq!(result, 467835);
+    }
+
+    #[test]
+    fn gears_need_exactly_two_numbers() {
         let lines = [
-            "467..114..",
-            "...*......",
-            "..35..633.",
-            "......#...",
-            "617*......",
-            ".....+.58.",
-            "..592.....",
-            "......755.",
-            "...$.*....",
-            ".664.598..",
+            "123...12.34",
+            ".*......*..",
+            "..45....6..",
         ];
-        let chars: Vec<char> = lines.into_iter().map(|l| l.chars()).flatten().collect();
+        // the first * touches three digits of 123 but that is still one number,
+        // the second touches 12, 34 and 6, so isn't a gear
+        let grid = Grid::parse_chars(&lines.join("\n"), '.', |c| c).unwrap();
 
-        let grid = chars.try_into().unwrap();
+        let result = gear_shift(&index(&grid, &Classifier::default()).unwrap()).unwrap();
+        assert_eq!(result, 123 * 45);
+    }
 
-        let result = search_grid(grid);
-        assert_eq!(result, 4361);
+    #[test]
+    fn gears_among_other_symbols() {
+        let grid = Grid::parse_chars(&EXAMPLE.replace('.', " "), ' ', |c| c).unwrap();
+        let classifier = Classifier { blank: ' ', symbols: Symbols::Punctuation };
+        assert_eq!(gear_shift(&index(&grid, &classifier).unwrap()).unwrap(), 467835);
+
+        // * isn't a symbol at all, let alone a gear
+        let classifier = Classifier { blank: ' ', symbols: Symbols::Set(vec!['#', '+', '$']) };
+        assert_eq!(gear_shift(&index(&grid, &classifier).unwrap()).unwrap(), 0);
     }
 }
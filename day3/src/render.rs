@@ -0,0 +1,248 @@
+//! The schematic drawn with its part numbers, other numbers and gears picked out,
+//! for a terminal, a web page or an image.
+use std::fmt::Write;
+use anyhow::Result;
+use common::grid::Grid;
+
+use crate::{rules::{self, PART_TWO}, schematic::Schematic};
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Cell {
+    Blank,
+    /// A digit of a number touching a symbol
+    Part,
+    /// A digit of a number touching no symbol
+    Loose,
+    Symbol,
+    /// A gear, with its ratio
+    Gear(usize),
+}
+
+/// What each cell of `grid` is, as `schematic` sees it,
+/// failing if a gear's ratio is too large for a `usize`
+pub fn annotate(grid: &Grid<char>, schematic: &Schematic) -> Result<Grid<Cell>> {
+    let mut cells = Grid::new(grid.width(), grid.height(), Cell::Blank);
+    for (id, number) in schematic.spans().iter().enumerate() {
+        let cell = match schematic.points_around(id).next() {
+            Some(_) => Cell::Part,
+            None => Cell::Loose,
+        };
+        for col in number.cols.clone() {
+            cells[(col, number.row)] = cell;
+        }
+    }
+    for (symbol, numbers) in schematic.points_with_spans() {
+        cells[(symbol.col, symbol.row)] = match PART_TWO.applies(symbol.value, numbers.len()) {
+            true => Cell::Gear(rules::product(symbol, &numbers)?),
+            false => Cell::Symbol,
+        };
+    }
+    Ok(cells)
+}
+
+const PART: &str = "\x1b[1;32m";
+const LOOSE: &str = "\x1b[1;31m";
+const GEAR: &str = "\x1b[1;30;43m";
+const RESET: &str = "\x1b[0m";
+
+fn ansi_style(cell: Cell) -> Option<&'static str> {
+    match cell {
+        Cell::Part => Some(PART),
+        Cell::Loose => Some(LOOSE),
+        Cell::Gear(_) => Some(GEAR),
+        Cell::Blank | Cell::Symbol => None,
+    }
+}
+
+/// The schematic for a terminal: part numbers green, other numbers red,
+/// and gears highlighted, with each row's gear ratios after it.
+pub fn ansi(grid: &Grid<char>, cells: &Grid<Cell>) -> String {
+    let mut out = String::new();
+    for row in 0..grid.height() {
+        let mut current = None;
+        let mut ratios = Vec::new();
+        for (&chr, &cell) in grid.row(row).iter().zip(cells.row(row)) {
+            let style = ansi_style(cell);
+            if style != current {
+                out.push_str(style.unwrap_or(RESET));
+                current = style;
+            }
+            out.push(chr);
+            if let Cell::Gear(ratio) = cell {
+                ratios.push(ratio.to_string());
+            }
+        }
+        if current.is_some() {
+            out.push_str(RESET);
+        }
+        if !ratios.is_empty() {
+            write!(out, "  {}{}{}", GEAR, ratios.join(" "), RESET).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn class(cell: Cell) -> Option<&'static str> {
+    match cell {
+        Cell::Part => Some("part"),
+        Cell::Loose => Some("loose"),
+        Cell::Symbol => Some("symbol"),
+        Cell::Gear(_) => Some("gear"),
+        Cell::Blank => None,
+    }
+}
+
+fn escape(chr: char, out: &mut String) {
+    match chr {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        _ => out.push(chr),
+    }
+}
+
+const STYLE: &str = ".part { color: #1a7f37; font-weight: bold; }
+.loose { color: #cf222e; }
+.symbol { font-weight: bold; }
+.gear { background: #ffd33d; font-weight: bold; }";
+
+/// The same view as [`ansi`] as a web page, each gear's ratio shown on hovering over it.
+pub fn html(grid: &Grid<char>, cells: &Grid<Cell>) -> String {
+    let mut out = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n{}\n</style>\n</head>\n<body>\n<pre>\n", STYLE);
+    for row in 0..grid.height() {
+        let mut current = None;
+        for (&chr, &cell) in grid.row(row).iter().zip(cells.row(row)) {
+            // every gear gets a span of its own, to carry its ratio
+            let gear = matches!(cell, Cell::Gear(_));
+            if class(cell) != current || gear {
+                if current.is_some() {
+                    out.push_str("</span>");
+                }
+                match cell {
+                    Cell::Gear(ratio) => write!(out, "<span class=\"gear\" title=\"gear ratio {}\">", ratio).unwrap(),
+                    _ => {
+                        if let Some(class) = class(cell) {
+                            write!(out, "<span class=\"{}\">", class).unwrap();
+                        }
+                    }
+                }
+                current = class(cell);
+            }
+            escape(chr, &mut out);
+        }
+        if current.is_some() {
+            out.push_str("</span>");
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+/// Size of a cell in the SVG, in pixels
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 16;
+
+/// The same view as [`ansi`] as an image, a cell per character,
+/// each gear's ratio shown on hovering over it.
+pub fn svg(grid: &Grid<char>, cells: &Grid<Cell>) -> String {
+    let (width, height) = (grid.width() * CELL_WIDTH, grid.height() * CELL_HEIGHT);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"14\">\n<style>\ntext {{ fill: #57606a; }}\n\
+         .part {{ fill: #1a7f37; font-weight: bold; }}\n.loose {{ fill: #cf222e; }}\n\
+         .symbol {{ fill: #24292f; font-weight: bold; }}\n.gear {{ fill: #24292f; font-weight: bold; }}\n\
+         rect {{ fill: #ffd33d; }}\n</style>\n",
+        w = width,
+        h = height
+    );
+    for row in 0..grid.height() {
+        for (col, (&chr, &cell)) in grid.row(row).iter().zip(cells.row(row)).enumerate() {
+            if chr.is_whitespace() {
+                continue;
+            }
+            let (x, y) = (col * CELL_WIDTH, row * CELL_HEIGHT);
+            if let Cell::Gear(ratio) = cell {
+                write!(
+                    out,
+                    "<g><title>gear ratio {}</title><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                    ratio, x, y, CELL_WIDTH, CELL_HEIGHT
+                )
+                .unwrap();
+            }
+            write!(out, "<text x=\"{}\" y=\"{}\"", x + 1, y + CELL_HEIGHT - 4).unwrap();
+            if let Some(class) = class(cell) {
+                write!(out, " class=\"{}\"", class).unwrap();
+            }
+            out.push('>');
+            escape(chr, &mut out);
+            out.push_str("</text>");
+            if let Cell::Gear(_) = cell {
+                out.push_str("</g>");
+            }
+            out.push('\n');
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schematic::index, symbols::Classifier};
+
+    fn annotated(lines: &[&str]) -> (Grid<char>, Grid<Cell>) {
+        let grid = Grid::parse_chars(&lines.join("\n"), '.', |c| c).unwrap();
+        let cells = annotate(&grid, &index(&grid, &Classifier::default()).unwrap()).unwrap();
+        (grid, cells)
+    }
+
+    #[test]
+    fn cells_of_a_schematic() {
+        let (_, cells) = annotated(&["12.7", "*...", "3&.."]);
+        assert_eq!(cells.row(0), &[Cell::Part, Cell::Part, Cell::Blank, Cell::Loose]);
+        assert_eq!(cells.row(1)[0], Cell::Gear(36));
+        assert_eq!(cells.row(2), &[Cell::Part, Cell::Symbol, Cell::Blank, Cell::Blank]);
+    }
+
+    #[test]
+    fn drawn_for_a_terminal() {
+        let (grid, cells) = annotated(&["12.7", "*...", "3&.."]);
+        assert_eq!(
+            ansi(&grid, &cells),
+            "\x1b[1;32m12\x1b[0m.\x1b[1;31m7\x1b[0m\n\
+             \x1b[1;30;43m*\x1b[0m...  \x1b[1;30;43m36\x1b[0m\n\
+             \x1b[1;32m3\x1b[0m&..\n"
+        );
+    }
+
+    #[test]
+    fn drawn_for_a_browser() {
+        let (grid, cells) = annotated(&["12.7", "*...", "3&.."]);
+        let html = html(&grid, &cells);
+        let body = &html[html.find("<pre>\n").unwrap() + 6..html.find("</pre>").unwrap()];
+        assert_eq!(
+            body,
+            "<span class=\"part\">12</span>.<span class=\"loose\">7</span>\n\
+             <span class=\"gear\" title=\"gear ratio 36\">*</span>...\n\
+             <span class=\"part\">3</span><span class=\"symbol\">&amp;</span>..\n"
+        );
+
+        let svg = svg(&grid, &cells);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"40\" height=\"48\""));
+        assert!(svg.contains("<g><title>gear ratio 36</title><rect x=\"0\" y=\"16\" width=\"10\" height=\"16\"/>\
+                              <text x=\"1\" y=\"28\" class=\"gear\">*</text></g>"));
+        assert!(svg.contains("<text x=\"11\" y=\"44\" class=\"symbol\">&amp;</text>"));
+        assert_eq!(svg.matches("<text").count(), 12);
+    }
+
+    #[test]
+    fn gear_ratio_too_large() {
+        let grid = Grid::parse_chars("9999999999*9999999999", '.', |c| c).unwrap();
+        let error = annotate(&grid, &index(&grid, &Classifier::default()).unwrap()).unwrap_err();
+        assert_eq!(error.to_string(), "the numbers around row 1, column 11 multiply to too much");
+    }
+}
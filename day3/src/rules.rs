@@ -0,0 +1,175 @@
+//! Answers made by combining the numbers around chosen symbols.
+//!
+//! A rule picks the symbols it applies to and how many numbers each must touch,
+//! then combines those numbers. `sum` adds every number touching a picked symbol,
+//! each once however many of them it touches; `product` and `max` combine each
+//! symbol's numbers on their own, adding up the results. Several rules add up too.
+use std::collections::BTreeSet;
+use anyhow::{anyhow, Context, Result};
+
+use crate::schematic::{Number, Schematic, Symbol};
+
+/// How many numbers a symbol must touch
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Neighbours {
+    /// At least one
+    Any,
+    Exactly(usize),
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Rule {
+    /// The symbol this applies to, every symbol if none
+    pub symbol: Option<char>,
+    pub neighbours: Neighbours,
+    pub aggregate: Aggregate,
+}
+
+/// Part one: every number touching any symbol
+pub const PART_ONE: Rule = Rule { symbol: None, neighbours: Neighbours::Any, aggregate: Aggregate::Sum };
+/// Part two: each `*` touching exactly two numbers, multiplied
+pub const PART_TWO: Rule = Rule { symbol: Some('*'), neighbours: Neighbours::Exactly(2), aggregate: Aggregate::Product };
+
+/// Parse a `symbol:neighbours:aggregate` rule, as given to `--rule`,
+/// where the symbol or neighbours may be `any`.
+pub fn parse_rule(rule: &str) -> Result<Rule> {
+    // from the right, so the symbol may itself be a `:`
+    let mut parts = rule.rsplitn(3, ':');
+    let (Some(aggregate), Some(neighbours), Some(symbol)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(anyhow!("expected symbol:neighbours:aggregate, not {}", rule));
+    };
+
+    let symbol = match symbol {
+        "any" => None,
+        _ => {
+            let mut chars = symbol.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => return Err(anyhow!("{} isn't a single symbol or any", symbol)),
+            }
+        }
+    };
+    let neighbours = match neighbours {
+        "any" => Neighbours::Any,
+        n => match n.parse().with_context(|| format!("{} isn't a count of numbers or any", n))? {
+            // a symbol touching no numbers has none to combine
+            0 => return Err(anyhow!("a rule needs at least one number, not {}", n)),
+            n => Neighbours::Exactly(n),
+        },
+    };
+    let aggregate = match aggregate {
+        "product" => Aggregate::Product,
+        "sum" => Aggregate::Sum,
+        "max" => Aggregate::Max,
+        other => return Err(anyhow!("{} isn't one of product, sum or max", other)),
+    };
+    Ok(Rule { symbol, neighbours, aggregate })
+}
+
+impl Rule {
+    /// Whether this rule picks `symbol` when it touches `neighbours` numbers
+    pub fn applies(&self, symbol: char, neighbours: usize) -> bool {
+        self.symbol.map_or(true, |s| s == symbol)
+            && match self.neighbours {
+                Neighbours::Any => neighbours > 0,
+                Neighbours::Exactly(n) => neighbours == n,
+            }
+    }
+
+    /// Fails if the answer, or a product along the way, is too large for a `usize`
+    pub fn apply(&self, schematic: &Schematic) -> Result<usize> {
+        let picked = schematic
+            .points_with_spans()
+            .filter(|(symbol, numbers)| self.applies(symbol.value, numbers.len()));
+        match self.aggregate {
+            Aggregate::Sum => {
+                // a number is the same number from whichever symbol finds it
+                let numbers: BTreeSet<_> = picked
+                    .flat_map(|(_, numbers)| numbers)
+                    .map(|n| (n.row, n.cols.start, n.value))
+                    .collect();
+                total(numbers.into_iter().map(|(_, _, value)| Ok(value)))
+            }
+            Aggregate::Product => total(picked.map(|(symbol, numbers)| product(symbol, &numbers))),
+            Aggregate::Max => total(picked.filter_map(|(_, numbers)| numbers.iter().map(|n| n.value).max()).map(Ok)),
+        }
+    }
+}
+
+/// The numbers around `symbol` multiplied, failing if that's too large for a `usize`
+pub fn product(symbol: &Symbol, numbers: &[&Number]) -> Result<usize> {
+    numbers
+        .iter()
+        .try_fold(1usize, |acc, n| acc.checked_mul(n.value))
+        .ok_or_else(|| anyhow!("the numbers around row {}, column {} multiply to too much", symbol.row + 1, symbol.col + 1))
+}
+
+/// Add up `values`, failing on the first that fails or overflows
+fn total(mut values: impl Iterator<Item = Result<usize>>) -> Result<usize> {
+    values.try_fold(0usize, |acc, value| acc.checked_add(value?).ok_or_else(|| anyhow!("the answer is too large")))
+}
+
+/// The sum of every rule's answer
+pub fn apply_all(rules: &[Rule], schematic: &Schematic) -> Result<usize> {
+    total(rules.iter().map(|rule| rule.apply(schematic)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::grid::Grid;
+    use crate::{schematic::index, symbols::Classifier, EXAMPLE};
+
+    #[test]
+    fn rules_from_args() {
+        assert_eq!(parse_rule("*:2:product").unwrap(), PART_TWO);
+        assert_eq!(parse_rule("any:any:sum").unwrap(), PART_ONE);
+        assert_eq!(
+            parse_rule("::3:max").unwrap(),
+            Rule { symbol: Some(':'), neighbours: Neighbours::Exactly(3), aggregate: Aggregate::Max }
+        );
+        assert_eq!(parse_rule("*:2").unwrap_err().to_string(), "expected symbol:neighbours:aggregate, not *:2");
+        assert_eq!(parse_rule("**:2:sum").unwrap_err().to_string(), "** isn't a single symbol or any");
+        assert!(parse_rule("*:two:sum").is_err());
+        assert!(parse_rule("*:2:mean").is_err());
+        assert_eq!(parse_rule("*:0:product").unwrap_err().to_string(), "a rule needs at least one number, not 0");
+    }
+
+    #[test]
+    fn generalised_gears() {
+        let grid = Grid::parse_chars(EXAMPLE, '.', |c| c).unwrap();
+        let schematic = index(&grid, &Classifier::default()).unwrap();
+
+        assert_eq!(PART_ONE.apply(&schematic).unwrap(), 4361);
+        assert_eq!(PART_TWO.apply(&schematic).unwrap(), 467835);
+        let rule = |text| parse_rule(text).unwrap();
+        // the lone 617 next to a *
+        assert_eq!(rule("*:1:sum").apply(&schematic).unwrap(), 617);
+        assert_eq!(rule("*:any:max").apply(&schematic).unwrap(), 467 + 617 + 755);
+        assert_eq!(rule("#:any:sum").apply(&schematic).unwrap(), 633);
+        assert_eq!(apply_all(&[rule("*:2:product"), rule("#:any:sum")], &schematic).unwrap(), 467835 + 633);
+    }
+
+    #[test]
+    fn sum_counts_each_number_once() {
+        // 12 touches both symbols
+        let grid = Grid::parse_chars("#12*\n....", '.', |c| c).unwrap();
+        let schematic = index(&grid, &Classifier::default()).unwrap();
+        assert_eq!(PART_ONE.apply(&schematic).unwrap(), 12);
+        assert_eq!(parse_rule("any:1:max").unwrap().apply(&schematic).unwrap(), 24);
+    }
+
+    #[test]
+    fn product_too_large() {
+        let grid = Grid::parse_chars("9999999999*9999999999", '.', |c| c).unwrap();
+        let schematic = index(&grid, &Classifier::default()).unwrap();
+        assert_eq!(PART_TWO.apply(&schematic).unwrap_err().to_string(), "the numbers around row 1, column 11 multiply to too much");
+    }
+}
@@ -0,0 +1,91 @@
+//! A schematic's numbers and symbols, indexed by which touch which.
+use anyhow::{anyhow, Result};
+use common::{adjacency::{Adjacency, Point, Span}, grid::Grid};
+use rayon::prelude::*;
+
+use crate::symbols::Classifier;
+
+/// A number in the schematic: the row it is on, the columns it covers, and its value
+pub type Number = Span<usize>;
+pub type Symbol = Point<char>;
+pub type Schematic = Adjacency<usize, char>;
+
+/// Every number in the schematic, row by row, failing on any too large for a `usize`
+pub fn find_numbers(grid: &Grid<char>) -> Result<Vec<Number>> {
+    let rows = (0..grid.height())
+        .into_par_iter()
+        .map(|row| {
+            let cells = grid.row(row);
+            let mut numbers = Vec::new();
+            let mut col = 0;
+            while col < cells.len() {
+                let digits = cells[col..].iter().take_while(|c| c.is_ascii_digit()).count();
+                if digits == 0 {
+                    col += 1;
+                    continue;
+                }
+                let value = cells[col..col + digits]
+                    .iter()
+                    .try_fold(0usize, |acc, d| acc.checked_mul(10)?.checked_add((*d as u8 - b'0') as usize))
+                    .ok_or_else(|| anyhow!("the number at row {}, column {} is too large", row + 1, col + 1))?;
+                numbers.push(Number { row, cols: col..col + digits, value });
+                col += digits;
+            }
+            Ok(numbers)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(rows.concat())
+}
+
+/// Every cell `classifier` calls a symbol, row by row
+pub fn find_symbols(grid: &Grid<char>, classifier: &Classifier) -> Vec<Symbol> {
+    (0..grid.height())
+        .flat_map(|row| grid.row(row).iter().enumerate().map(move |(col, &value)| Symbol { row, col, value }))
+        .filter(|symbol| classifier.is_symbol(symbol.value))
+        .collect()
+}
+
+pub fn index(grid: &Grid<char>, classifier: &Classifier) -> Result<Schematic> {
+    Ok(Schematic::new(find_numbers(grid)?, find_symbols(grid, classifier)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_in_a_row() {
+        let grid = Grid::parse_chars("467..114..\n..35.....7", '.', |c| c).unwrap();
+        let numbers = find_numbers(&grid).unwrap();
+        assert_eq!(numbers, vec![
+            Number { row: 0, cols: 0..3, value: 467 },
+            Number { row: 0, cols: 5..8, value: 114 },
+            Number { row: 1, cols: 2..4, value: 35 },
+            Number { row: 1, cols: 9..10, value: 7 },
+        ]);
+    }
+
+    #[test]
+    fn symbols_and_their_numbers() {
+        let grid = Grid::parse_chars("467..114..\n...*.....#\n..35..633.", '.', |c| c).unwrap();
+        let schematic = index(&grid, &Classifier::default()).unwrap();
+        assert_eq!(schematic.points(), &[
+            Symbol { row: 1, col: 3, value: '*' },
+            Symbol { row: 1, col: 9, value: '#' },
+        ]);
+
+        let values = |numbers: Vec<&Number>| numbers.iter().map(|n| n.value).collect::<Vec<_>>();
+        assert_eq!(values(schematic.spans_around(1, 3)), vec![467, 35]);
+        assert_eq!(values(schematic.spans_around(1, 9)), vec![633]);
+        // 114 touches no symbol
+        assert_eq!(schematic.points_around(1).count(), 0);
+        assert_eq!(schematic.points_touching(2).map(|(symbol, _)| symbol.value).collect::<Vec<_>>(), vec!['*']);
+    }
+
+    #[test]
+    fn number_too_large() {
+        let grid = Grid::parse_chars("1*..................\n99999999999999999999", '.', |c| c).unwrap();
+        let error = find_numbers(&grid).unwrap_err();
+        assert_eq!(error.to_string(), "the number at row 2, column 1 is too large");
+    }
+}
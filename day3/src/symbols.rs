@@ -0,0 +1,57 @@
+//! Which characters in a schematic are symbols, which are blank, and which are neither.
+
+/// Characters that count as symbols, besides the blank and digits which never do
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Symbols {
+    /// ASCII punctuation, as in the puzzle
+    Punctuation,
+    /// Exactly these
+    Set(Vec<char>),
+    /// Anything else that isn't whitespace, letters and other alphabets included
+    Unicode,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Classifier {
+    pub blank: char,
+    pub symbols: Symbols,
+}
+
+impl Default for Classifier {
+    fn default() -> Self {
+        Classifier { blank: '.', symbols: Symbols::Punctuation }
+    }
+}
+
+impl Classifier {
+    pub fn is_symbol(&self, cell: char) -> bool {
+        if cell == self.blank || cell.is_ascii_digit() {
+            return false;
+        }
+        match &self.symbols {
+            Symbols::Punctuation => cell.is_ascii_punctuation(),
+            Symbols::Set(set) => set.contains(&cell),
+            Symbols::Unicode => !cell.is_whitespace(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_cells() {
+        let puzzle = Classifier::default();
+        assert!(puzzle.is_symbol('#') && puzzle.is_symbol('*'));
+        assert!(!puzzle.is_symbol('.') && !puzzle.is_symbol('7') && !puzzle.is_symbol('a') && !puzzle.is_symbol('★'));
+
+        let set = Classifier { blank: ' ', symbols: Symbols::Set(vec!['#', 'x']) };
+        assert!(set.is_symbol('x') && set.is_symbol('#'));
+        assert!(!set.is_symbol('*') && !set.is_symbol(' '));
+
+        let unicode = Classifier { blank: '·', symbols: Symbols::Unicode };
+        assert!(unicode.is_symbol('★') && unicode.is_symbol('é') && unicode.is_symbol('.'));
+        assert!(!unicode.is_symbol('·') && !unicode.is_symbol('3') && !unicode.is_symbol(' '));
+    }
+}
@@ -0,0 +1,85 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{u8, space1},
+    combinator::all_consuming,
+    error::Error,
+    Finish,
+    IResult,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, tuple, pair},
+};
+use tinyset::Set64;
+use rayon::prelude::*;
+
+pub type CardID = u8;
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: CardID,
+    pub winning: Set64<u8>,
+    pub picked: Vec<u8>,
+}
+
+impl Card {
+    /// How many of the picked numbers are winners
+    pub fn matches(&self) -> usize {
+        self.picked.iter().filter(|pick| self.winning.contains(**pick)).count()
+    }
+}
+
+fn parse_card_id(input: &str) -> IResult<&str, CardID> {
+    delimited(
+        tuple((tag("Card"), space1)),
+        u8,
+        tuple((tag(":"), space1)),
+    )(input)
+}
+
+fn parse_number_sequence(input: &str) -> IResult<&str, Vec<u8>> {
+    separated_list1(
+        space1,
+        u8,
+    )(input)
+}
+
+fn parse_lottery_numbers(input: &str) -> IResult<&str, (Vec<u8>, Vec<u8>)> {
+    separated_pair(
+        parse_number_sequence,
+        tuple((space1, tag("|"), space1)),
+        parse_number_sequence
+    )(input)
+}
+
+pub fn parse_card(input: &str) -> Result<Card, Error<&str>> {
+    let (_, (id, (winning_nums, picked))) = all_consuming(pair(
+        parse_card_id,
+        parse_lottery_numbers
+    ))(input).finish()?;
+
+    let winning = winning_nums.into_iter().collect();
+    Ok(Card { id, winning, picked })
+}
+
+/// Every card, in order
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, Error<&str>> {
+    input.par_lines().map(parse_card).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn card_from_line() {
+        let card = parse_card("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1").unwrap();
+        assert_eq!(card.id, 3);
+        assert_eq!(card.picked, vec![69, 82, 63, 72, 16, 21, 14, 1]);
+        assert_eq!(card.matches(), 2);
+    }
+
+    #[test]
+    fn missing_separator() {
+        let result = parse_card("Card 1: 41 48 83 86 17 83 86  6 31 17  9 48 53");
+        assert_eq!(result.unwrap_err(), Error::new("", nom::error::ErrorKind::Space));
+    }
+}
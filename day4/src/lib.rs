@@ -0,0 +1,28 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use rayon::prelude::*;
+
+pub mod card;
+pub mod part_one;
+pub mod part_two;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Parsed<'a> = Vec<card::Card>;
+    type PartOne = usize;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        card::parse_cards(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(cards: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(cards.par_iter().map(part_one::score).sum())
+    }
+
+    fn part_two(cards: &Self::Parsed<'_>) -> Result<u32> {
+        Ok(part_two::count_cards(cards))
+    }
+}
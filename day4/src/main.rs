@@ -1,22 +1,17 @@
-use std::fs;
-use rayon::prelude::*;
 use anyhow::Result;
+use common::{Part, Solution};
+use day4::Day4;
 
 mod cli;
-mod part_one;
-mod part_two;
 
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
 
-    let sum: usize = match &args.part_two {
-        false => lines.map(part_one::parse_line).sum(),
-        true => part_two::parse_input(lines) as usize,
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    
-    println!("Value is: {}", sum);
-    Ok(()) 
+
+    args.output.print_each(&args.paths, |input| Day4::run(part, input))
 }
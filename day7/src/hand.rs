@@ -0,0 +1,89 @@
+use nom::{
+    character::complete::{space1, u16},
+    IResult, Finish,
+    sequence::separated_pair, multi::count, combinator::{all_consuming, value}, branch::alt, bytes::complete::tag,
+    error::Error,
+};
+use rayon::prelude::*;
+
+/// A card as written, ordered as in part one
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Label {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    T,
+    J,
+    Q,
+    K,
+    A
+}
+
+/// A hand of cards and what was bid on it
+#[derive(Debug)]
+pub struct Deal {
+    pub labels: [Label; 5],
+    pub bid: u16,
+}
+
+fn parse_label(input: &str) -> IResult<&str, Label> {
+    alt((
+        value(Label::Two, tag("2")),
+        value(Label::Three, tag("3")),
+        value(Label::Four, tag("4")),
+        value(Label::Five, tag("5")),
+        value(Label::Six, tag("6")),
+        value(Label::Seven, tag("7")),
+        value(Label::Eight, tag("8")),
+        value(Label::Nine, tag("9")),
+        value(Label::T, tag("T")),
+        value(Label::J, tag("J")),
+        value(Label::Q, tag("Q")),
+        value(Label::K, tag("K")),
+        value(Label::A, tag("A")),
+    ))(input)
+}
+
+fn parse_labels(input: &str) -> IResult<&str, [Label; 5]> {
+    let (remainder, labels_vec) = count(parse_label, 5)(input)?;
+    Ok((remainder, labels_vec.try_into().unwrap()))
+}
+
+pub fn parse_deal(input: &str) -> Result<Deal, Error<&str>> {
+    let (_, (labels, bid)) = all_consuming(separated_pair(
+        parse_labels,
+        space1,
+        u16
+    ))(input).finish()?;
+    Ok(Deal { labels, bid })
+}
+
+/// Every deal, in order
+pub fn parse_deals(input: &str) -> Result<Vec<Deal>, Error<&str>> {
+    input.par_lines().map(parse_deal).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deal_from_line() {
+        let deal = parse_deal("KTJJT 220").unwrap();
+        assert_eq!(deal.labels, [Label::K, Label::T, Label::J, Label::J, Label::T]);
+        assert_eq!(deal.bid, 220);
+    }
+
+    #[test]
+    fn unknown_card() {
+        let input = "32T3K 765\nT55X5 684\n";
+        let deals = parse_deals(input);
+        assert_eq!(deals.unwrap_err(), Error::new("X5 684", nom::error::ErrorKind::Tag));
+    }
+}
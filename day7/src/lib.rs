@@ -0,0 +1,27 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+
+pub mod hand;
+pub mod part_one;
+pub mod part_two;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Parsed<'a> = Vec<hand::Deal>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        hand::parse_deals(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(deals: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_one::winnings(deals))
+    }
+
+    fn part_two(deals: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_two::winnings(deals))
+    }
+}
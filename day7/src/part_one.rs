@@ -1,11 +1,9 @@
-use nom::{
-    character::complete::{space1, u16},
-    IResult, 
-    sequence::separated_pair, multi::count, combinator::value, branch::alt, bytes::complete::tag,
-};
-use rayon::{prelude::*, str::Lines};
+use rayon::prelude::*;
 use std::cmp::Ordering;
 
+// part one ranks cards as they're written
+use crate::hand::{Deal, Label as Card};
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 enum Kind {
     HighCard,
@@ -17,23 +15,6 @@ enum Kind {
     FiveOfAKind
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
-enum Card {
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
-    T,
-    J,
-    Q,
-    K,
-    A
-}
-
 #[derive(Eq, Debug)]
 struct Hand {
     bid: u16,
@@ -90,61 +71,18 @@ fn kind_from_cards(cards: [Card; 5]) -> Kind {
     }
 }
 
-fn parse_card(input: &str) -> IResult<&str, Card> {
-    alt((
-        value(Card::Two, tag("2")),
-        value(Card::Three, tag("3")),
-        value(Card::Four, tag("4")),
-        value(Card::Five, tag("5")),
-        value(Card::Six, tag("6")),
-        value(Card::Seven, tag("7")),
-        value(Card::Eight, tag("8")),
-        value(Card::Nine, tag("9")),
-        value(Card::T, tag("T")),
-        value(Card::J, tag("J")),
-        value(Card::Q, tag("Q")),
-        value(Card::K, tag("K")),
-        value(Card::A, tag("A")),
-    ))(input)
-}
-
-fn parse_cards(input: &str) -> IResult<&str, [Card; 5]> {
-    let (remainder, cards_vec) = count(parse_card, 5)(input)?;
-    Ok((remainder, cards_vec.try_into().unwrap()))
-}
-
-fn parse_line(input: &str) -> IResult<&str, ([Card; 5], u16)> {
-    separated_pair(
-        parse_cards,
-        space1,
-        u16
-    )(input)
-}
-
-pub fn parse_input(lines: Lines) -> usize {
-    let mut hands: Vec<_> = lines
-        .into_par_iter()
-        .filter_map(|l| match parse_line(l) {
-            Ok((_, (cards, bid))) => Some(
-                Hand { 
-                    cards, 
-                    kind: kind_from_cards(cards), 
-                    bid 
-                }),
-            Err(e) => {println!("{:#?}", e); None},
+pub fn winnings(deals: &[Deal]) -> usize {
+    let mut hands: Vec<Hand> = deals
+        .par_iter()
+        .map(|deal| Hand {
+            cards: deal.labels,
+            kind: kind_from_cards(deal.labels),
+            bid: deal.bid
         })
         .collect();
 
-    
-
     hands.par_sort();
 
-    for idx in 0..hands.len() { 
-        let hand = &hands[idx];
-
-        println!("{} {:?} score: {}",idx, hand, hand.bid as usize * (idx + 1))
-    }
-
     hands.par_iter()
         .enumerate()
         .map(|(i, hand)| {(i + 1) * hand.bid as usize})
@@ -155,7 +93,8 @@ pub fn parse_input(lines: Lines) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-        
+    use crate::hand::{parse_deal, parse_deals};
+
     #[test]
     fn rank_and_bid_from_line() {
         let lines = [
@@ -166,7 +105,7 @@ mod tests {
             ("QQQJA 483", Kind::ThreeOfAKind),
         ];
         for (line, expectation) in lines {
-            let (_, (cards, _)) = parse_line(line).unwrap();
+            let cards = parse_deal(line).unwrap().labels;
             let kind = kind_from_cards(cards);
             println!("{:?} {:#?}", cards, kind);
             assert_eq!(kind, expectation);
@@ -174,9 +113,9 @@ mod tests {
     }
 
     #[test]
-    fn winnings() {
+    fn total_winnings() {
         let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
-        let winnings = parse_input(input.par_lines());
-        assert_eq!(winnings, 6440);
+        let deals = parse_deals(input).unwrap();
+        assert_eq!(winnings(&deals), 6440);
     }
 }
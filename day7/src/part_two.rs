@@ -1,11 +1,8 @@
-use nom::{
-    character::complete::{space1, u16},
-    IResult, 
-    sequence::separated_pair, multi::count, combinator::value, branch::alt, bytes::complete::tag,
-};
-use rayon::{prelude::*, str::Lines};
+use rayon::prelude::*;
 use std::cmp::Ordering;
 
+use crate::hand::{Deal, Label};
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 enum Kind {
     HighCard,
@@ -17,6 +14,7 @@ enum Kind {
     FiveOfAKind
 }
 
+/// A card as ranked in part two, with jokers lowest
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 enum Card {
     J,
@@ -96,67 +94,41 @@ fn kind_from_cards(cards: [Card; 5]) -> Kind {
     }
 }
 
-fn parse_card(input: &str) -> IResult<&str, Card> {
-    alt((
-        value(Card::N2, tag("2")),
-        value(Card::N3, tag("3")),
-        value(Card::N4, tag("4")),
-        value(Card::N5, tag("5")),
-        value(Card::N6, tag("6")),
-        value(Card::N7, tag("7")),
-        value(Card::N8, tag("8")),
-        value(Card::N9, tag("9")),
-        value(Card::T, tag("T")),
-        value(Card::J, tag("J")),
-        value(Card::Q, tag("Q")),
-        value(Card::K, tag("K")),
-        value(Card::A, tag("A")),
-    ))(input)
-}
-
-fn parse_cards(input: &str) -> IResult<&str, [Card; 5]> {
-    let (remainder, cards_vec) = count(parse_card, 5)(input)?;
-    Ok((remainder, cards_vec.try_into().unwrap()))
-}
-
-fn parse_line(input: &str) -> IResult<&str, ([Card; 5], u16)> {
-    separated_pair(
-        parse_cards,
-        space1,
-        u16
-    )(input)
+impl From<Label> for Card {
+    fn from(label: Label) -> Self {
+        match label {
+            Label::Two => Card::N2,
+            Label::Three => Card::N3,
+            Label::Four => Card::N4,
+            Label::Five => Card::N5,
+            Label::Six => Card::N6,
+            Label::Seven => Card::N7,
+            Label::Eight => Card::N8,
+            Label::Nine => Card::N9,
+            Label::T => Card::T,
+            Label::J => Card::J,
+            Label::Q => Card::Q,
+            Label::K => Card::K,
+            Label::A => Card::A,
+        }
+    }
 }
 
-pub fn parse_input(lines: Lines) -> usize {
-    let mut hands: Vec<_> = lines
-        .into_par_iter()
-        .filter_map(|l| match parse_line(l) {
-            Ok((_, (cards, bid))) => Some(
-                Hand { 
-                    cards, 
-                    kind: kind_from_cards(cards), 
-                    bid 
-                }),
-            Err(e) => {println!("{:#?}", e); None},
+pub fn winnings(deals: &[Deal]) -> usize {
+    let mut hands: Vec<Hand> = deals
+        .par_iter()
+        .map(|deal| {
+            let cards = deal.labels.map(Card::from);
+            Hand {
+                cards,
+                kind: kind_from_cards(cards),
+                bid: deal.bid
+            }
         })
         .collect();
 
-    
-
     hands.par_sort();
 
-    for idx in 0..hands.len() { 
-        let hand = &hands[idx];
-
-        println!("cards: {:?} kind: {:?} position: {} bid: {} score: {}",
-            hand.cards,
-            hand.kind,
-            idx + 1,
-            hand.bid,
-            hand.bid as usize * (idx + 1)
-        )
-    }
-
     hands.par_iter()
         .enumerate()
         .map(|(i, hand)| {(i + 1) * hand.bid as usize})
@@ -167,7 +139,8 @@ pub fn parse_input(lines: Lines) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-        
+    use crate::hand::{parse_deal, parse_deals};
+
     #[test]
     fn rank_and_bid_from_line() {
         let lines = [
@@ -178,7 +151,7 @@ mod tests {
             ("QQQJA 483", Kind::FourOfAKind),
         ];
         for (line, expectation) in lines {
-            let (_, (cards, _)) = parse_line(line).unwrap();
+            let cards = parse_deal(line).unwrap().labels.map(Card::from);
             let kind = kind_from_cards(cards);
             println!("{:?} {:#?}", cards, kind);
             assert_eq!(kind, expectation);
@@ -186,9 +159,9 @@ mod tests {
     }
 
     #[test]
-    fn winnings() {
+    fn total_winnings() {
         let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
-        let winnings = parse_input(input.par_lines());
-        assert_eq!(winnings, 5905);
+        let deals = parse_deals(input).unwrap();
+        assert_eq!(winnings(&deals), 5905);
     }
 }
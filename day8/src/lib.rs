@@ -0,0 +1,27 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+
+pub mod network;
+pub mod part_one;
+pub mod part_two;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Parsed<'a> = network::Network<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        network::parse_network(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(network: &Self::Parsed<'_>) -> Result<usize> {
+        Ok(part_one::steps(network))
+    }
+
+    fn part_two(network: &Self::Parsed<'_>) -> Result<usize> {
+        part_two::steps(network)
+    }
+}
@@ -1,20 +1,17 @@
-use std::fs;
 use anyhow::Result;
+use common::{Part, Solution};
+use day8::Day8;
 
 mod cli;
-mod part_one;
-mod part_two;
 
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
 
-    let sum: usize = match &args.part_two {
-        false => part_one::parse_input(&file),
-        true => part_two::parse_input(&file),
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    
-    println!("Value is: {}", sum);
-    Ok(()) 
+
+    args.output.print_each(&args.paths, |input| Day8::run(part, input))
 }
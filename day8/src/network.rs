@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use nom::{
+    character::complete::alphanumeric1,
+    IResult, Finish,
+    sequence::{separated_pair, delimited}, multi::many1, combinator::{all_consuming, value}, branch::alt, bytes::complete::tag,
+    error::Error,
+};
+use rayon::prelude::*;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Direction {
+    Left,
+    Right
+}
+
+/// The directions to follow, and each node's left and right neighbours
+#[derive(Debug)]
+pub struct Network<'a> {
+    pub directions: Vec<Direction>,
+    pub nodes: BTreeMap<&'a str, (&'a str, &'a str)>,
+}
+
+fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
+    many1(
+        alt((
+            value(Direction::Left, tag("L")), 
+            value(Direction::Right, tag("R"))
+        ))
+    )(input)
+}
+
+fn parse_map(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tag(" = "), 
+        delimited(
+            tag("("), 
+            separated_pair(
+                alphanumeric1, 
+                tag(", "), 
+                alphanumeric1
+            ), 
+            tag(")")
+        )
+    )(input)
+}
+
+pub fn parse_network(input: &str) -> Result<Network, Error<&str>> {
+    let lines: Vec<_> = input.par_lines().collect();
+
+    let (directions_raw, maps_raw) = lines.split_at(lines.len().min(2));
+
+    let (_, directions) = all_consuming(parse_directions)(directions_raw.first().unwrap_or(&input)).finish()?;
+    let maps = maps_raw
+        .into_par_iter()
+        .map(|l| all_consuming(parse_map)(l).finish().map(|(_, n)| n))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Network { directions, nodes: maps.into_iter().collect() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_from_input() {
+        let network = parse_network("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(network.directions, vec![Direction::Left, Direction::Left, Direction::Right]);
+        assert_eq!(network.nodes.get("BBB"), Some(&("AAA", "ZZZ")));
+        assert_eq!(network.nodes.len(), 3);
+    }
+
+    #[test]
+    fn malformed_node() {
+        let input = "RL\n\nAAA = (BBB, CCC)\nBBB = DDD, EEE)\n";
+        let network = parse_network(input);
+        assert_eq!(network.unwrap_err(), Error::new("DDD, EEE)", nom::error::ErrorKind::Tag));
+    }
+}
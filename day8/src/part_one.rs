@@ -1,57 +1,7 @@
-use std::collections::BTreeMap;
+use crate::network::{Direction, Network};
 
-use nom::{
-    character::complete::alpha1,
-    IResult, 
-    sequence::{separated_pair, delimited}, multi::many1, combinator::value, branch::alt, bytes::complete::tag,
-};
-use rayon::prelude::*;
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
-enum Direction {
-    Left,
-    Right
-}
-
-fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
-    many1(
-        alt((
-            value(Direction::Left, tag("L")), 
-            value(Direction::Right, tag("R"))
-        ))
-    )(input)
-}
-
-fn parse_map(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
-        alpha1,
-        tag(" = "), 
-        delimited(
-            tag("("), 
-            separated_pair(
-                alpha1, 
-                tag(", "), 
-                alpha1
-            ), 
-            tag(")")
-        )
-    )(input)
-}
-
-pub fn parse_input(input: &str) -> usize {
-    let lines: Vec<_> = input.par_lines().collect();
-
-    let (directions_raw, maps_raw) = lines.split_at(2);
-
-    let (_, directions) = parse_directions(directions_raw[0]).unwrap();
-    let maps = maps_raw
-        .into_par_iter()
-        .filter_map(|l| match parse_map(*l) {
-            Ok((_, n)) => Some(n),
-            Err(e) => panic!("{}", e),
-        });
-
-    let map = BTreeMap::from_par_iter(maps);
+pub fn steps(network: &Network) -> usize {
+    let Network { directions, nodes: map } = network;
 
     let max_direction = directions.len();
     let mut iterations = 0;
@@ -74,18 +24,19 @@ pub fn parse_input(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::network::parse_network;
+
     #[test]
     fn sample_input_1() {
         let input = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)\n";
-        let steps = parse_input(input);
-        assert_eq!(steps, 2);
+        let network = parse_network(input).unwrap();
+        assert_eq!(steps(&network), 2);
     }
 
     #[test]
     fn sample_input_2() {
         let input = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
-        let steps = parse_input(input);
-        assert_eq!(steps, 6);
+        let network = parse_network(input).unwrap();
+        assert_eq!(steps(&network), 6);
     }
 }
@@ -1,62 +1,14 @@
-use std::collections::BTreeMap;
-
-use nom::{
-    character::complete::alphanumeric1,
-    IResult, 
-    sequence::{separated_pair, delimited}, multi::many1, combinator::value, branch::alt, bytes::complete::tag,
-};
+use anyhow::{anyhow, Result};
 use rayon::prelude::*;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
-enum Direction {
-    Left,
-    Right
-}
-
-fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
-    many1(
-        alt((
-            value(Direction::Left, tag("L")), 
-            value(Direction::Right, tag("R"))
-        ))
-    )(input)
-}
-
-fn parse_map(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
-        alphanumeric1,
-        tag(" = "), 
-        delimited(
-            tag("("), 
-            separated_pair(
-                alphanumeric1, 
-                tag(", "), 
-                alphanumeric1
-            ), 
-            tag(")")
-        )
-    )(input)
-}
-
-pub fn parse_input(input: &str) -> usize {
-    let lines: Vec<_> = input.par_lines().collect();
-
-    let (directions_raw, maps_raw) = lines.split_at(2);
+use crate::network::{Direction, Network};
 
-    let (_, directions) = parse_directions(directions_raw[0]).unwrap();
-    let maps = maps_raw
-        .into_par_iter()
-        .filter_map(|l| match parse_map(*l) {
-            Ok((_, n)) => Some(n),
-            Err(e) => panic!("{}", e),
-        });
-
-    let map = BTreeMap::from_par_iter(maps);
+pub fn steps(network: &Network) -> Result<usize> {
+    let Network { directions, nodes: map } = network;
 
     let max_direction = directions.len();
     let mut iterations = 0;
     let mut positions: Vec<&str> = map.keys().filter(|k| k.ends_with('A')).map(|s| *s).collect();
-    let start_positions = positions.clone();
     let target = positions.len();
     let mut periods = vec![Vec::new(); target];
     loop {
@@ -84,38 +36,32 @@ pub fn parse_input(input: &str) -> usize {
             }
         }
         iterations += 1;
-        println!("{:?}", periods);
         if periods.iter().filter(|ps| ps.len() > 4).count() == target {
             break;
         };
     };
 
-    let mut simple_periods = Vec::new();
-    for (prds, start) in periods.iter().zip(start_positions) {
-        println!("Start {} reached Z at {}", start, prds[0]);
-        simple_periods.push(prds[0]);
-        for idx in 1..prds.len() {
-            let a = prds[idx - 1];
-            let b = prds[idx];
-            let gap = b - a;
-            println!("Start {} reached Z again at {} after {}", start, b, gap);
-        }
-    }
-
-    println!("Calculating LCM");
-    let lcm = simple_periods.into_iter().reduce(|a, b| num_integer::lcm(a, b));
+    // each start reaches its Z at a steady period, its first
+    let lcm = periods.iter().map(|prds| prds[0]).reduce(|a, b| num_integer::lcm(a, b));
 
-    lcm.unwrap()
+    lcm.ok_or_else(|| anyhow!("no starting node ends in A"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::network::parse_network;
+
     #[test]
     fn sample_input() {
         let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\n";
-        let steps = parse_input(input);
-        assert_eq!(steps, 6);
+        let network = parse_network(input).unwrap();
+        assert_eq!(steps(&network).unwrap(), 6);
+    }
+
+    #[test]
+    fn no_starting_nodes() {
+        let network = parse_network("LR\n\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n").unwrap();
+        assert_eq!(steps(&network).unwrap_err().to_string(), "no starting node ends in A");
     }
 }
@@ -0,0 +1,40 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::i32,
+    combinator::all_consuming,
+    error::Error,
+    Finish,
+    IResult, 
+    multi::separated_list1, 
+};
+use rayon::prelude::*;
+
+fn parse_line_to_nums(input: &str) -> IResult<&str, Vec<i32>> {
+    separated_list1(tag(" "), i32)(input)
+}
+
+pub fn parse_history(line: &str) -> Result<Vec<i32>, Error<&str>> {
+    let (_, nums) = all_consuming(parse_line_to_nums)(line).finish()?;
+    Ok(nums)
+}
+
+/// Every line's readings, in order
+pub fn parse_histories(input: &str) -> Result<Vec<Vec<i32>>, Error<&str>> {
+    input.par_lines().map(parse_history).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_from_line() {
+        assert_eq!(parse_history("0 -3 6"), Ok(vec![0, -3, 6]));
+    }
+
+    #[test]
+    fn trailing_garbage() {
+        let actual = parse_history("1 3 x");
+        assert_eq!(actual, Err(Error::new(" x", nom::error::ErrorKind::Eof)));
+    }
+}
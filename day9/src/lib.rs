@@ -0,0 +1,28 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use rayon::prelude::*;
+
+pub mod history;
+pub mod part_one;
+pub mod part_two;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Parsed<'a> = Vec<Vec<i32>>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        history::parse_histories(input).map_err(|e| ParseError::locate(input, e).into())
+    }
+
+    fn part_one(histories: &Self::Parsed<'_>) -> Result<i32> {
+        Ok(histories.par_iter().map(|history| part_one::calculate_next_value(history)).sum())
+    }
+
+    fn part_two(histories: &Self::Parsed<'_>) -> Result<i32> {
+        Ok(histories.par_iter().map(|history| part_two::calculate_next_value(history)).sum())
+    }
+}
@@ -1,25 +1,17 @@
-use std::{fs, time::Instant};
 use anyhow::Result;
-use rayon::prelude::*;
+use common::{Part, Solution};
+use day9::Day9;
 
 mod cli;
-mod part_one;
-mod part_two;
 
 
 fn main() -> Result<()> {
     let args = cli::parse();
-    let file = fs::read_to_string(&args.path)?;
-    let lines = file.par_lines();
 
-    let timer = Instant::now();
-    let sum: i32 = match &args.part_two {
-        false => lines.into_par_iter().map(part_one::parse_line).sum(),
-        true => lines.into_par_iter().map(part_two::parse_line).sum(),
-        // true => part_two::parse_input(&file),
+    let part = match &args.part_two {
+        false => Part::One,
+        true => Part::Two,
     };
-    let base_time = timer.elapsed();
-    
-    println!("Value is: {} calculated in {}", sum, base_time.as_micros());
-    Ok(()) 
+
+    args.output.print_each(&args.paths, |input| Day9::run(part, input))
 }
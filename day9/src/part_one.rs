@@ -1,17 +1,7 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::i32,
-    IResult, 
-    multi::separated_list1, 
-};
 use packed_simd::{i32x4, shuffle};
 // use rayon::prelude::*;
 
-fn parse_line_to_nums(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(tag(" "), i32)(input)
-}
-
-fn calculate_next_value(raw_nums: Vec<i32>) -> i32 {
+pub fn calculate_next_value(raw_nums: &[i32]) -> i32 {
     let input_size = raw_nums.len();
     // Establish how many chunks of 4 i32s we will need
     let number_of_chunks = input_size.div_ceil(4);
@@ -133,21 +123,11 @@ fn calculate_next_value(raw_nums: Vec<i32>) -> i32 {
     }
 }
 
-pub fn parse_line(line: &str) -> i32 {
-    match parse_line_to_nums(line) {
-        Ok((_, nums)) => calculate_next_value(nums),
-        Err(e) => {
-            println!("{}", e); 
-            return 0
-            // panic!();
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::history::parse_history;
+
     #[test]
     fn test_parse_line() {
         let inputs = [
@@ -156,11 +136,10 @@ mod tests {
             ("10 13 16 21 30 45", 68),
         ];
         for (input, expected) in inputs {
-            let actual = parse_line(input);
-            println!("expecting {} actual {}", expected, actual);
+            let actual = calculate_next_value(&parse_history(input).unwrap());
+            println!("expecting {} actual {:?}", expected, actual);
             assert_eq!(actual, expected);
         }
         
     }
-
 }